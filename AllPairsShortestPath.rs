@@ -0,0 +1,145 @@
+// Graph_new.rs と一緒に使う
+
+// 隣接行列上の Floyd-Warshall
+// 入力の mat[u][v] == max_value() は辺がないことを表す
+#[derive(Clone, Debug)]
+struct FloydWarshall<Cost> {
+    dist: Vec<Vec<Cost>>,
+    // next[u][v]: u から v への最短路で u の次に通る頂点
+    next: Vec<Vec<usize>>,
+}
+
+impl<Cost> FloydWarshall<Cost>
+where
+    Cost: Ord + Zero + Max + CheckedAdd + Copy,
+{
+    fn new(mat: Vec<Vec<Cost>>) -> FloydWarshall<Cost> {
+        let n = mat.len();
+        let mut dist = mat;
+        let mut next = vec![vec![n; n]; n];
+        for u in 0..n {
+            for v in 0..n {
+                if dist[u][v] != Cost::max_value() {
+                    next[u][v] = v;
+                }
+            }
+            if dist[u][u] > Cost::zero() {
+                dist[u][u] = Cost::zero();
+                next[u][u] = u;
+            }
+        }
+        for k in 0..n {
+            for u in 0..n {
+                if dist[u][k] == Cost::max_value() {
+                    continue;
+                }
+                for v in 0..n {
+                    if dist[k][v] == Cost::max_value() {
+                        continue;
+                    }
+                    if let Some(d) = dist[u][k].checked_add(&dist[k][v]) {
+                        if d < dist[u][v] {
+                            dist[u][v] = d;
+                            next[u][v] = next[u][k];
+                        }
+                    }
+                }
+            }
+        }
+        FloydWarshall {
+            dist: dist,
+            next: next,
+        }
+    }
+
+    fn from_graph<G: Graph<Cost = Cost>>(g: &G) -> FloydWarshall<Cost> {
+        let n = g.len();
+        let mut mat = vec![vec![Cost::max_value(); n]; n];
+        for u in 0..n {
+            for e in g.get(u) {
+                if e.cost < mat[u][e.to] {
+                    mat[u][e.to] = e.cost;
+                }
+            }
+        }
+        Self::new(mat)
+    }
+
+    fn len(&self) -> usize {
+        self.dist.len()
+    }
+
+    fn has_negative_cycle(&self) -> bool {
+        (0..self.len()).any(|v| self.dist[v][v] < Cost::zero())
+    }
+
+    fn dist(&self, u: usize, v: usize) -> Distance<Cost> {
+        if self.dist[u][v] == Cost::max_value() {
+            return Distance::Unreachable;
+        }
+        let n = self.len();
+        for k in 0..n {
+            if self.dist[k][k] < Cost::zero()
+                && self.dist[u][k] != Cost::max_value()
+                && self.dist[k][v] != Cost::max_value()
+            {
+                return Distance::NegativeInfinity;
+            }
+        }
+        Distance::Finite(self.dist[u][v])
+    }
+
+    // u から v への最短路の頂点列. 到達不能か -inf なら None
+    fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        match self.dist(u, v) {
+            Distance::Finite(_) => {}
+            _ => return None,
+        }
+        let mut res = vec![u];
+        let mut p = u;
+        while p != v {
+            p = self.next[p][v];
+            res.push(p);
+        }
+        Some(res)
+    }
+}
+
+// Johnson 法
+// 負閉路があれば None, そうでなければ res[u][v] (到達不能なら max_value())
+fn johnson<Cost>(g: &AdjList<Cost>) -> Option<Vec<Vec<Cost>>>
+where
+    Cost: Ord + Add<Cost, Output = Cost> + Sub<Cost, Output = Cost> + Zero + Max + CheckedAdd + Copy,
+{
+    let n = g.len();
+    let mut aug = g.clone();
+    aug.adj.push(Vec::new());
+    for v in 0..n {
+        aug.add_edge(n, v, Cost::zero());
+    }
+    let (h, neg) = aug.bellmanford(n);
+    if neg.iter().any(|&f| f) {
+        return None;
+    }
+
+    let mut reweighted = AdjList::new(n);
+    for u in 0..n {
+        for e in g.get(u) {
+            reweighted.add_edge(u, e.to, e.cost + h[u] - h[e.to]);
+        }
+    }
+    let mut res = Vec::with_capacity(n);
+    for u in 0..n {
+        let (dist, _) = reweighted.get_shortest_path(u);
+        res.push(
+            (0..n)
+                .map(|v| if dist[v] == Cost::max_value() {
+                    dist[v]
+                } else {
+                    dist[v] - h[u] + h[v]
+                })
+                .collect(),
+        );
+    }
+    Some(res)
+}
@@ -0,0 +1,186 @@
+// Graph_new.rs と一緒に使う
+// 距離は辺の本数 (0-1 BFS では重みの和) で, ShortestPath<usize> として返す
+
+// neighbors(v, &mut buf): v から出る辺の (行き先, 重み 0 or 1) を buf に積む
+fn zero_one_bfs_by<F>(n: usize, sources: &[usize], mut neighbors: F) -> ShortestPath<usize>
+where
+    F: FnMut(usize, &mut Vec<(usize, usize)>),
+{
+    let mut dist = vec![std::usize::MAX; n];
+    let mut pre = vec![None; n];
+    let mut deque = std::collections::VecDeque::new();
+    for &s in sources {
+        if dist[s] != 0 {
+            dist[s] = 0;
+            deque.push_back((s, 0));
+        }
+    }
+    let mut buf = Vec::new();
+    while let Some((p, d)) = deque.pop_front() {
+        if d > dist[p] {
+            continue;
+        }
+        buf.clear();
+        neighbors(p, &mut buf);
+        for &(to, w) in &buf {
+            debug_assert!(w <= 1);
+            if d + w < dist[to] {
+                dist[to] = d + w;
+                pre[to] = Some(p);
+                if w == 0 {
+                    deque.push_front((to, d));
+                } else {
+                    deque.push_back((to, d + 1));
+                }
+            }
+        }
+    }
+    ShortestPath {
+        dist: dist,
+        pre: pre,
+    }
+}
+
+fn bfs_by<F>(n: usize, sources: &[usize], mut neighbors: F) -> ShortestPath<usize>
+where
+    F: FnMut(usize, &mut Vec<usize>),
+{
+    let mut dist = vec![std::usize::MAX; n];
+    let mut pre = vec![None; n];
+    let mut queue = std::collections::VecDeque::new();
+    for &s in sources {
+        if dist[s] != 0 {
+            dist[s] = 0;
+            queue.push_back(s);
+        }
+    }
+    let mut buf = Vec::new();
+    while let Some(p) = queue.pop_front() {
+        buf.clear();
+        neighbors(p, &mut buf);
+        for &to in &buf {
+            if dist[to] == std::usize::MAX {
+                dist[to] = dist[p] + 1;
+                pre[to] = Some(p);
+                queue.push_back(to);
+            }
+        }
+    }
+    ShortestPath {
+        dist: dist,
+        pre: pre,
+    }
+}
+
+trait GraphBFS: Graph {
+    // 辺の重みを無視して辺の本数で測る
+    fn bfs(&self, from: usize) -> ShortestPath<usize>;
+    fn bfs_multi(&self, sources: &[usize]) -> ShortestPath<usize>;
+    // 重みが zero() の辺を 0, それ以外を 1 とみなす
+    fn zero_one_bfs(&self, from: usize) -> ShortestPath<usize>;
+    fn zero_one_bfs_multi(&self, sources: &[usize]) -> ShortestPath<usize>;
+}
+
+impl<T> GraphBFS for T
+where
+    T: Graph,
+    T::Cost: Zero + PartialEq,
+{
+    fn bfs(&self, from: usize) -> ShortestPath<usize> {
+        self.bfs_multi(&[from])
+    }
+
+    fn bfs_multi(&self, sources: &[usize]) -> ShortestPath<usize> {
+        bfs_by(self.len(), sources, |p, buf| {
+            buf.extend(self.get(p).map(|e| e.to));
+        })
+    }
+
+    fn zero_one_bfs(&self, from: usize) -> ShortestPath<usize> {
+        self.zero_one_bfs_multi(&[from])
+    }
+
+    fn zero_one_bfs_multi(&self, sources: &[usize]) -> ShortestPath<usize> {
+        let zero = T::Cost::zero();
+        zero_one_bfs_by(self.len(), sources, |p, buf| {
+            buf.extend(self.get(p).map(|e| (e.to, if e.cost == zero { 0 } else { 1 })));
+        })
+    }
+}
+
+// 迷路をそのまま 4 近傍のグラフとして扱う. マス (i, j) の番号は i * w + j
+struct GridGraph<'a> {
+    grid: &'a Vec<Vec<char>>,
+    h: usize,
+    w: usize,
+}
+
+impl<'a> GridGraph<'a> {
+    fn new(grid: &'a Vec<Vec<char>>) -> GridGraph<'a> {
+        let h = grid.len();
+        let w = if h == 0 { 0 } else { grid[0].len() };
+        GridGraph {
+            grid: grid,
+            h: h,
+            w: w,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.h * self.w
+    }
+
+    fn id(&self, i: usize, j: usize) -> usize {
+        i * self.w + j
+    }
+
+    fn pos(&self, v: usize) -> (usize, usize) {
+        (v / self.w, v % self.w)
+    }
+
+    fn adjacent(&self, v: usize) -> Vec<usize> {
+        let (i, j) = self.pos(v);
+        let mut res = Vec::with_capacity(4);
+        if i > 0 {
+            res.push(v - self.w);
+        }
+        if i + 1 < self.h {
+            res.push(v + self.w);
+        }
+        if j > 0 {
+            res.push(v - 1);
+        }
+        if j + 1 < self.w {
+            res.push(v + 1);
+        }
+        res
+    }
+
+    // wall のマスには入らない
+    fn bfs(&self, sources: &[(usize, usize)], wall: char) -> ShortestPath<usize> {
+        let sources: Vec<usize> = sources.iter().map(|&(i, j)| self.id(i, j)).collect();
+        bfs_by(self.len(), &sources, |p, buf| {
+            for to in self.adjacent(p) {
+                let (i, j) = self.pos(to);
+                if self.grid[i][j] != wall {
+                    buf.push(to);
+                }
+            }
+        })
+    }
+
+    // cost(from, to): 移動のコスト (0 か 1), None なら移動できない
+    fn zero_one_bfs<F>(&self, sources: &[(usize, usize)], cost: F) -> ShortestPath<usize>
+    where
+        F: Fn((usize, usize), (usize, usize)) -> Option<usize>,
+    {
+        let sources: Vec<usize> = sources.iter().map(|&(i, j)| self.id(i, j)).collect();
+        zero_one_bfs_by(self.len(), &sources, |p, buf| {
+            for to in self.adjacent(p) {
+                if let Some(c) = cost(self.pos(p), self.pos(to)) {
+                    buf.push((to, c));
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,202 @@
+// Graph_new.rs と一緒に使う
+// 左側の頂点 v (0 <= v < g.len()) から右側の頂点 e.to (0 <= e.to < n_right) への辺を持つグラフを受け取る
+#[derive(Clone, Debug)]
+struct HopcroftKarp {
+    match_left: Vec<Option<usize>>,
+    match_right: Vec<Option<usize>>,
+    size: usize,
+}
+
+impl HopcroftKarp {
+    fn new<G: Graph>(g: &G, n_right: usize) -> HopcroftKarp {
+        let n = g.len();
+        let mut res = HopcroftKarp {
+            match_left: vec![None; n],
+            match_right: vec![None; n_right],
+            size: 0,
+        };
+        let mut level = vec![0; n];
+        let mut iter = vec![0; n];
+        loop {
+            if !res.bfs(g, &mut level) {
+                break;
+            }
+            for v in iter.iter_mut() {
+                *v = 0;
+            }
+            for v in 0..n {
+                if res.match_left[v].is_none() && res.dfs(g, v, &level, &mut iter) {
+                    res.size += 1;
+                }
+            }
+        }
+        res
+    }
+
+    // 未マッチの左頂点からの交互路の長さで level をつける. 増加路があれば true
+    fn bfs<G: Graph>(&self, g: &G, level: &mut Vec<usize>) -> bool {
+        let n = g.len();
+        let mut queue = std::collections::VecDeque::new();
+        for v in 0..n {
+            if self.match_left[v].is_none() {
+                level[v] = 0;
+                queue.push_back(v);
+            } else {
+                level[v] = std::usize::MAX;
+            }
+        }
+        let mut found = false;
+        while let Some(v) = queue.pop_front() {
+            for e in g.get(v) {
+                match self.match_right[e.to] {
+                    None => found = true,
+                    Some(w) => if level[w] == std::usize::MAX {
+                        level[w] = level[v] + 1;
+                        queue.push_back(w);
+                    },
+                }
+            }
+        }
+        found
+    }
+
+    fn dfs<G: Graph>(&mut self, g: &G, v: usize, level: &Vec<usize>, iter: &mut Vec<usize>) -> bool {
+        // slice::Iter の nth は O(1)
+        while let Some(u) = g.get(v).nth(iter[v]).map(|e| e.to) {
+            iter[v] += 1;
+            let ok = match self.match_right[u] {
+                None => true,
+                Some(w) => level[w] == level[v] + 1 && self.dfs(g, w, level, iter),
+            };
+            if ok {
+                self.match_left[v] = Some(u);
+                self.match_right[u] = Some(v);
+                return true;
+            }
+        }
+        false
+    }
+
+    // マッチングの辺 (左, 右) の列
+    fn matching(&self) -> Vec<(usize, usize)> {
+        self.match_left
+            .iter()
+            .enumerate()
+            .filter_map(|(v, &u)| u.map(|u| (v, u)))
+            .collect()
+    }
+
+    // 未マッチの左頂点から交互路でたどれる頂点 (左, 右)
+    fn reachable<G: Graph>(&self, g: &G) -> (Vec<bool>, Vec<bool>) {
+        let mut left = vec![false; g.len()];
+        let mut right = vec![false; self.match_right.len()];
+        let mut stack: Vec<usize> = (0..g.len()).filter(|&v| self.match_left[v].is_none()).collect();
+        for &v in &stack {
+            left[v] = true;
+        }
+        while let Some(v) = stack.pop() {
+            for e in g.get(v) {
+                if right[e.to] || self.match_left[v] == Some(e.to) {
+                    continue;
+                }
+                right[e.to] = true;
+                if let Some(w) = self.match_right[e.to] {
+                    if !left[w] {
+                        left[w] = true;
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+        (left, right)
+    }
+
+    // 最小頂点被覆 (左の頂点, 右の頂点). 大きさはマッチングと等しい (König の定理)
+    fn min_vertex_cover<G: Graph>(&self, g: &G) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.reachable(g);
+        (
+            (0..left.len()).filter(|&v| !left[v]).collect(),
+            (0..right.len()).filter(|&u| right[u]).collect(),
+        )
+    }
+
+    // 最大独立集合 (最小頂点被覆の補集合)
+    fn max_independent_set<G: Graph>(&self, g: &G) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.reachable(g);
+        (
+            (0..left.len()).filter(|&v| left[v]).collect(),
+            (0..right.len()).filter(|&u| !right[u]).collect(),
+        )
+    }
+}
+
+// Hungarian 法 O(n^2 m)
+// 行 i を相異なる列 assign[i] に割り当ててコストの和を最小化する (行数 n <= 列数 m)
+fn hungarian(cost: &Vec<Vec<i64>>) -> (i64, Vec<usize>) {
+    let n = cost.len();
+    if n == 0 {
+        return (0, vec![]);
+    }
+    let m = cost[0].len();
+    assert!(n <= m);
+    let inf = std::i64::MAX / 2;
+    // 1-indexed で, 列 0 は番兵
+    let mut u = vec![0; n + 1];
+    let mut v = vec![0; m + 1];
+    let mut p = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..n + 1 {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![inf; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = inf;
+            let mut j1 = 0;
+            for j in 1..m + 1 {
+                if used[j] {
+                    continue;
+                }
+                let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if cur < minv[j] {
+                    minv[j] = cur;
+                    way[j] = j0;
+                }
+                if minv[j] < delta {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..m + 1 {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+    let mut assign = vec![0; n];
+    for j in 1..m + 1 {
+        if p[j] != 0 {
+            assign[p[j] - 1] = j - 1;
+        }
+    }
+    let total = (0..n).map(|i| cost[i][assign[i]]).sum();
+    (total, assign)
+}
@@ -0,0 +1,263 @@
+trait Monoid {
+    fn unity() -> Self;
+    fn op(&self, other: &Self) -> Self;
+}
+
+// 添字の範囲 [lo, hi) が巨大な場合でも, 触ったノードだけを作る
+// 子の添字 0 は「まだ作っていない」を表す (根が 0 番なので子にはならない)
+#[derive(Clone, Debug)]
+struct DynamicSegmentTree<T> {
+    dat: Vec<T>,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    lo: i64,
+    hi: i64,
+}
+
+impl<T: Monoid + std::clone::Clone> DynamicSegmentTree<T> {
+    fn new(lo: i64, hi: i64) -> DynamicSegmentTree<T> {
+        assert!(lo < hi);
+        DynamicSegmentTree {
+            dat: vec![T::unity()],
+            left: vec![0],
+            right: vec![0],
+            lo: lo,
+            hi: hi,
+        }
+    }
+
+    fn with_capacity(lo: i64, hi: i64, cap: usize) -> DynamicSegmentTree<T> {
+        let mut res = Self::new(lo, hi);
+        res.dat.reserve(cap);
+        res.left.reserve(cap);
+        res.right.reserve(cap);
+        res
+    }
+
+    fn new_node(&mut self) -> usize {
+        self.dat.push(T::unity());
+        self.left.push(0);
+        self.right.push(0);
+        self.dat.len() - 1
+    }
+
+    fn update(&mut self, i: i64, val: T) {
+        assert!(self.lo <= i && i < self.hi);
+        let (lo, hi) = (self.lo, self.hi);
+        let mut path = Vec::new();
+        let mut v = 0;
+        let (mut a, mut b) = (lo, hi);
+        while b - a > 1 {
+            path.push(v);
+            let m = a + (b - a) / 2;
+            if i < m {
+                if self.left[v] == 0 {
+                    let c = self.new_node();
+                    self.left[v] = c;
+                }
+                v = self.left[v];
+                b = m;
+            } else {
+                if self.right[v] == 0 {
+                    let c = self.new_node();
+                    self.right[v] = c;
+                }
+                v = self.right[v];
+                a = m;
+            }
+        }
+        self.dat[v] = val;
+        while let Some(v) = path.pop() {
+            self.dat[v] = self.get_node(self.left[v]).op(&self.get_node(self.right[v]));
+        }
+    }
+
+    fn get_node(&self, v: usize) -> T {
+        if v == 0 {
+            T::unity()
+        } else {
+            self.dat[v].clone()
+        }
+    }
+
+    fn get(&self, i: i64) -> T {
+        assert!(self.lo <= i && i < self.hi);
+        let mut v = 0;
+        let (mut a, mut b) = (self.lo, self.hi);
+        while b - a > 1 {
+            let m = a + (b - a) / 2;
+            v = if i < m {
+                b = m;
+                self.left[v]
+            } else {
+                a = m;
+                self.right[v]
+            };
+            if v == 0 {
+                return T::unity();
+            }
+        }
+        self.dat[v].clone()
+    }
+
+    fn query(&self, l: i64, r: i64) -> T {
+        if l >= r {
+            return T::unity();
+        }
+        self.query_(l, r, 0, self.lo, self.hi)
+    }
+
+    fn query_(&self, l: i64, r: i64, v: usize, a: i64, b: i64) -> T {
+        if l <= a && b <= r {
+            self.dat[v].clone()
+        } else if !(r <= a || b <= l) {
+            let m = a + (b - a) / 2;
+            let retl = if self.left[v] == 0 {
+                T::unity()
+            } else {
+                self.query_(l, r, self.left[v], a, m)
+            };
+            let retr = if self.right[v] == 0 {
+                T::unity()
+            } else {
+                self.query_(l, r, self.right[v], m, b)
+            };
+            retl.op(&retr)
+        } else {
+            T::unity()
+        }
+    }
+}
+
+// 遅延評価つき
+// change: lazy から dat へ変更を伝播させる関数 (区間 [l, r) を受け取る)
+#[derive(Clone, Debug)]
+struct DynamicLazySegmentTree<T, U, F> {
+    dat: Vec<T>,
+    lazy: Vec<U>,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    change: F,
+    lo: i64,
+    hi: i64,
+}
+
+impl<T, U, F> DynamicLazySegmentTree<T, U, F>
+where
+    T: Monoid + std::clone::Clone,
+    U: Monoid + std::clone::Clone,
+    F: FnMut(&mut T, U, i64, i64),
+{
+    fn new(lo: i64, hi: i64, change: F) -> DynamicLazySegmentTree<T, U, F> {
+        assert!(lo < hi);
+        DynamicLazySegmentTree {
+            dat: vec![T::unity()],
+            lazy: vec![U::unity()],
+            left: vec![0],
+            right: vec![0],
+            change: change,
+            lo: lo,
+            hi: hi,
+        }
+    }
+
+    fn new_node(&mut self) -> usize {
+        self.dat.push(T::unity());
+        self.lazy.push(U::unity());
+        self.left.push(0);
+        self.right.push(0);
+        self.dat.len() - 1
+    }
+
+    fn propagate(&mut self, v: usize, a: i64, b: i64) {
+        if b - a <= 1 {
+            return;
+        }
+        if self.left[v] == 0 {
+            let c = self.new_node();
+            self.left[v] = c;
+        }
+        if self.right[v] == 0 {
+            let c = self.new_node();
+            self.right[v] = c;
+        }
+        let mut x = U::unity();
+        std::mem::swap(&mut x, &mut self.lazy[v]);
+        let m = a + (b - a) / 2;
+        let (lv, rv) = (self.left[v], self.right[v]);
+        (self.change)(&mut self.dat[lv], x.clone(), a, m);
+        self.lazy[lv] = self.lazy[lv].op(&x);
+        (self.change)(&mut self.dat[rv], x.clone(), m, b);
+        self.lazy[rv] = self.lazy[rv].op(&x);
+    }
+
+    fn range_update(&mut self, l: i64, r: i64, x: U) {
+        if l >= r {
+            return;
+        }
+        let (lo, hi) = (self.lo, self.hi);
+        self.range_update_(0, lo, hi, l, r, x);
+    }
+
+    fn range_update_(&mut self, v: usize, a: i64, b: i64, l: i64, r: i64, x: U) {
+        if r <= a || b <= l {
+            return;
+        }
+        if l <= a && b <= r {
+            (self.change)(&mut self.dat[v], x.clone(), a, b);
+            self.lazy[v] = self.lazy[v].op(&x);
+            return;
+        }
+        self.propagate(v, a, b);
+        let m = a + (b - a) / 2;
+        let (lv, rv) = (self.left[v], self.right[v]);
+        self.range_update_(lv, a, m, l, r, x.clone());
+        self.range_update_(rv, m, b, l, r, x);
+        self.dat[v] = self.dat[lv].op(&self.dat[rv]);
+    }
+
+    fn update(&mut self, i: i64, val: T) {
+        assert!(self.lo <= i && i < self.hi);
+        let (lo, hi) = (self.lo, self.hi);
+        self.update_(0, lo, hi, i, val);
+    }
+
+    fn update_(&mut self, v: usize, a: i64, b: i64, i: i64, val: T) {
+        if b - a == 1 {
+            self.dat[v] = val;
+            return;
+        }
+        self.propagate(v, a, b);
+        let m = a + (b - a) / 2;
+        let (lv, rv) = (self.left[v], self.right[v]);
+        if i < m {
+            self.update_(lv, a, m, i, val);
+        } else {
+            self.update_(rv, m, b, i, val);
+        }
+        self.dat[v] = self.dat[lv].op(&self.dat[rv]);
+    }
+
+    fn query(&mut self, l: i64, r: i64) -> T {
+        if l >= r {
+            return T::unity();
+        }
+        let (lo, hi) = (self.lo, self.hi);
+        self.query_(l, r, 0, lo, hi)
+    }
+
+    fn query_(&mut self, l: i64, r: i64, v: usize, a: i64, b: i64) -> T {
+        if l <= a && b <= r {
+            self.dat[v].clone()
+        } else if !(r <= a || b <= l) {
+            self.propagate(v, a, b);
+            let m = a + (b - a) / 2;
+            let (lv, rv) = (self.left[v], self.right[v]);
+            let retl = self.query_(l, r, lv, a, m);
+            let retr = self.query_(l, r, rv, m, b);
+            retl.op(&retr)
+        } else {
+            T::unity()
+        }
+    }
+}
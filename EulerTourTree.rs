@@ -0,0 +1,242 @@
+// LinkCutTree.rs と一緒に使う (Monoid は LinkCutTree.rs のものを使う)
+// 頂点に値を持つ森. 部分木の積を取るので可換なモノイドを仮定する
+// 木をオイラーツアー (頂点 v と有向辺 (u, v) の列) として splay 木で持つ
+// ノード 0..n は頂点, それ以降は有向辺
+#[derive(Clone, Debug)]
+struct EulerTourTree<T> {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    par: Vec<usize>,
+    val: Vec<T>,
+    sum: Vec<T>,
+    arcs: std::collections::HashMap<(usize, usize), usize>,
+    // 使い終わった有向辺のノード
+    free: Vec<usize>,
+}
+
+impl<T: Monoid + Clone> EulerTourTree<T> {
+    const NIL: usize = std::usize::MAX;
+
+    fn new(n: usize) -> EulerTourTree<T> {
+        EulerTourTree::from_vec(vec![T::unity(); n])
+    }
+
+    fn from_vec(v: Vec<T>) -> EulerTourTree<T> {
+        let n = v.len();
+        EulerTourTree {
+            left: vec![Self::NIL; n],
+            right: vec![Self::NIL; n],
+            par: vec![Self::NIL; n],
+            sum: v.clone(),
+            val: v,
+            arcs: std::collections::HashMap::new(),
+            free: vec![],
+        }
+    }
+
+    fn new_node(&mut self) -> usize {
+        match self.free.pop() {
+            Some(x) => x,
+            None => {
+                self.left.push(Self::NIL);
+                self.right.push(Self::NIL);
+                self.par.push(Self::NIL);
+                self.val.push(T::unity());
+                self.sum.push(T::unity());
+                self.val.len() - 1
+            }
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let (l, r) = (self.left[x], self.right[x]);
+        let mut sum = self.val[x].clone();
+        if l != Self::NIL {
+            sum = self.sum[l].op(&sum);
+        }
+        if r != Self::NIL {
+            sum = sum.op(&self.sum[r]);
+        }
+        self.sum[x] = sum;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.par[x];
+        let g = self.par[p];
+        if g != Self::NIL {
+            if self.left[g] == p {
+                self.left[g] = x;
+            } else {
+                self.right[g] = x;
+            }
+        }
+        self.par[x] = g;
+        if self.left[p] == x {
+            let b = self.right[x];
+            self.left[p] = b;
+            if b != Self::NIL {
+                self.par[b] = p;
+            }
+            self.right[x] = p;
+        } else {
+            let b = self.left[x];
+            self.right[p] = b;
+            if b != Self::NIL {
+                self.par[b] = p;
+            }
+            self.left[x] = p;
+        }
+        self.par[p] = x;
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        while self.par[x] != Self::NIL {
+            let p = self.par[x];
+            let g = self.par[p];
+            if g != Self::NIL {
+                if (self.left[g] == p) == (self.left[p] == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    // x の直前で切り, (x より前, x 以降) の根を返す
+    fn split_before(&mut self, x: usize) -> (usize, usize) {
+        self.splay(x);
+        let l = self.left[x];
+        if l != Self::NIL {
+            self.par[l] = Self::NIL;
+            self.left[x] = Self::NIL;
+            self.update(x);
+        }
+        (l, x)
+    }
+
+    // x の直後で切り, (x まで, x より後) の根を返す
+    fn split_after(&mut self, x: usize) -> (usize, usize) {
+        self.splay(x);
+        let r = self.right[x];
+        if r != Self::NIL {
+            self.par[r] = Self::NIL;
+            self.right[x] = Self::NIL;
+            self.update(x);
+        }
+        (x, r)
+    }
+
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == Self::NIL {
+            return b;
+        }
+        if b == Self::NIL {
+            return a;
+        }
+        let mut x = a;
+        while self.right[x] != Self::NIL {
+            x = self.right[x];
+        }
+        self.splay(x);
+        self.right[x] = b;
+        self.par[b] = x;
+        self.update(x);
+        x
+    }
+
+    // ツアーを v から始まるように回す
+    fn reroot(&mut self, v: usize) -> usize {
+        let (l, r) = self.split_before(v);
+        self.merge(r, l)
+    }
+
+    fn connected(&mut self, u: usize, v: usize) -> bool {
+        if u == v {
+            return true;
+        }
+        self.splay(v);
+        // u から splay 木の根までたどり, 同じ木なら根は v
+        let mut x = u;
+        while self.par[x] != Self::NIL {
+            x = self.par[x];
+        }
+        self.splay(u);
+        x == v
+    }
+
+    // 辺 (u, v) を張る. 既に連結なら何もせず false
+    fn link(&mut self, u: usize, v: usize) -> bool {
+        if self.connected(u, v) {
+            return false;
+        }
+        let uv = self.new_node();
+        let vu = self.new_node();
+        self.arcs.insert((u, v), uv);
+        self.arcs.insert((v, u), vu);
+        let a = self.reroot(u);
+        let b = self.reroot(v);
+        let a = self.merge(a, uv);
+        let a = self.merge(a, b);
+        self.merge(a, vu);
+        true
+    }
+
+    // 辺 (u, v) を切る. 辺がなければ何もせず false
+    fn cut(&mut self, u: usize, v: usize) -> bool {
+        let (uv, vu) = match (self.arcs.remove(&(u, v)), self.arcs.remove(&(v, u))) {
+            (Some(uv), Some(vu)) => (uv, vu),
+            _ => return false,
+        };
+        // u から始めると (u, v) が (v, u) より前に来る: A (u, v) B (v, u) C
+        self.reroot(u);
+        let (a, rest) = self.split_before(uv);
+        let (_, rest) = self.split_after(rest);
+        // B はそのまま v 側の木になる
+        let (_, rest) = self.split_before(vu);
+        let (_, c) = self.split_after(rest);
+        self.merge(a, c);
+        for &x in &[uv, vu] {
+            self.left[x] = Self::NIL;
+            self.right[x] = Self::NIL;
+            self.par[x] = Self::NIL;
+            self.free.push(x);
+        }
+        true
+    }
+
+    fn get(&self, v: usize) -> T {
+        self.val[v].clone()
+    }
+
+    fn set(&mut self, v: usize, x: T) {
+        self.splay(v);
+        self.val[v] = x;
+        self.update(v);
+    }
+
+    // v を含む木全体の積
+    fn component_fold(&mut self, v: usize) -> T {
+        self.splay(v);
+        self.sum[v].clone()
+    }
+
+    // 辺 (v, p) を切ったときの v 側, つまり p を親としたときの v の部分木の積. 辺がなければ None
+    fn subtree_fold(&mut self, v: usize, p: usize) -> Option<T> {
+        let (pv, vp) = match (self.arcs.get(&(p, v)), self.arcs.get(&(v, p))) {
+            (Some(&pv), Some(&vp)) => (pv, vp),
+            _ => return None,
+        };
+        // p から始めると A (p, v) B (v, p) C で, B が v の部分木
+        self.reroot(p);
+        let (a, _) = self.split_after(pv);
+        let (b, c) = self.split_before(vp);
+        let res = if b == Self::NIL { T::unity() } else { self.sum[b].clone() };
+        let b = self.merge(b, c);
+        self.merge(a, b);
+        Some(res)
+    }
+}
@@ -0,0 +1,140 @@
+#[derive(Clone, Debug)]
+struct FenwickTree<T> {
+    dat: Vec<T>,
+}
+
+impl<T> FenwickTree<T>
+where
+    T: algebra::Group + std::marker::Copy,
+{
+    fn new(n: usize) -> Self {
+        FenwickTree {
+            dat: vec![T::zero(); n + 1],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.dat.len() - 1
+    }
+
+    fn add(&mut self, i: usize, v: T) {
+        assert!(i < self.len());
+        let mut i = i + 1;
+        while i < self.dat.len() {
+            self.dat[i] = self.dat[i] + v;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // [0, r) の和
+    fn sum(&self, r: usize) -> T {
+        let mut res = T::zero();
+        let mut i = r;
+        while i > 0 {
+            res = res + self.dat[i];
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    // [l, r) の和
+    fn range_sum(&self, l: usize, r: usize) -> T {
+        self.sum(r) - self.sum(l)
+    }
+
+    // sum(r) >= w となる最小の r (全要素が非負のときのみ正しい)
+    // 存在しなければ len()+1 を返す
+    fn lower_bound(&self, w: T) -> usize
+    where
+        T: Ord,
+    {
+        if w <= T::zero() {
+            return 0;
+        }
+        let n = self.len();
+        let mut k = 1;
+        while k * 2 <= n {
+            k *= 2;
+        }
+        let mut pos = 0;
+        let mut acc = T::zero();
+        while k > 0 {
+            if pos + k <= n && acc + self.dat[pos + k] < w {
+                pos += k;
+                acc = acc + self.dat[pos];
+            }
+            k /= 2;
+        }
+        pos + 1
+    }
+}
+
+// 区間加算・区間和
+// sum(r) = b0(r) * r + b1(r) となるように 2 本の BIT を持つ
+#[derive(Clone, Debug)]
+struct RangeAddFenwickTree<T> {
+    b0: FenwickTree<T>,
+    b1: FenwickTree<T>,
+}
+
+impl<T> RangeAddFenwickTree<T>
+where
+    T: algebra::Group + std::ops::Mul<T, Output = T> + MapToi64 + std::marker::Copy,
+{
+    fn new(n: usize) -> Self {
+        RangeAddFenwickTree {
+            b0: FenwickTree::new(n + 1),
+            b1: FenwickTree::new(n + 1),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.b0.len() - 1
+    }
+
+    // [l, r) に v を足す
+    fn add(&mut self, l: usize, r: usize, v: T) {
+        assert!(l <= r && r <= self.len());
+        self.b0.add(l, v);
+        // algebra::Group の Neg は当てにならないので 0 から引く
+        self.b0.add(r, T::zero() - v);
+        self.b1.add(l, T::zero() - v * T::map_to_number(l as i64));
+        self.b1.add(r, v * T::map_to_number(r as i64));
+    }
+
+    // [0, r) の和
+    fn sum(&self, r: usize) -> T {
+        self.b0.sum(r) * T::map_to_number(r as i64) + self.b1.sum(r)
+    }
+
+    fn range_sum(&self, l: usize, r: usize) -> T {
+        self.sum(r) - self.sum(l)
+    }
+}
+
+// i < j かつ a[i] > a[j] となる組の数
+// i64 は algebra::Group を実装していないので, 個数は Vec<i64> の BIT で数える
+fn inversion_number<T: Ord + Clone>(a: &Vec<T>) -> i64 {
+    let mut vals = a.clone();
+    vals.sort();
+    vals.dedup();
+    let mut bit = vec![0i64; vals.len() + 1];
+    let mut res = 0;
+    for (i, e) in a.iter().enumerate() {
+        let k = vals.binary_search(e).unwrap();
+        // a[0..i] のうち a[i] 以下の個数
+        let mut cnt = 0;
+        let mut j = k + 1;
+        while j > 0 {
+            cnt += bit[j];
+            j -= j & j.wrapping_neg();
+        }
+        res += i as i64 - cnt;
+        let mut j = k + 1;
+        while j < bit.len() {
+            bit[j] += 1;
+            j += j & j.wrapping_neg();
+        }
+    }
+    res
+}
@@ -0,0 +1,154 @@
+// 密な H x W のグリッド上で一点加算・長方形和
+#[derive(Clone, Debug)]
+struct FenwickTree2D<T> {
+    dat: Vec<Vec<T>>,
+    h: usize,
+    w: usize,
+}
+
+impl<T> FenwickTree2D<T>
+where
+    T: algebra::Group + std::marker::Copy,
+{
+    fn new(h: usize, w: usize) -> Self {
+        FenwickTree2D {
+            dat: vec![vec![T::zero(); w + 1]; h + 1],
+            h: h,
+            w: w,
+        }
+    }
+
+    fn add(&mut self, x: usize, y: usize, v: T) {
+        assert!(x < self.h && y < self.w);
+        let mut i = x + 1;
+        while i <= self.h {
+            let mut j = y + 1;
+            while j <= self.w {
+                self.dat[i][j] = self.dat[i][j] + v;
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // [0, x) x [0, y) の和
+    fn sum(&self, x: usize, y: usize) -> T {
+        let mut res = T::zero();
+        let mut i = x;
+        while i > 0 {
+            let mut j = y;
+            while j > 0 {
+                res = res + self.dat[i][j];
+                j -= j & j.wrapping_neg();
+            }
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    // [lx, rx) x [ly, ry) の和 (CumSum2D::query と同じ引数順)
+    fn query(&self, lx: usize, ly: usize, rx: usize, ry: usize) -> T {
+        self.sum(rx, ry) - self.sum(rx, ly) - self.sum(lx, ry) + self.sum(lx, ly)
+    }
+}
+
+// 更新が起こりうる点をすべて先に与えておく (オフライン)
+// x 座標のセグメント木の各ノードに y 座標のソート列と BIT を持たせる
+#[derive(Clone, Debug)]
+struct RangeTree2D<T> {
+    xs: Vec<i64>,
+    ys: Vec<Vec<i64>>,
+    bit: Vec<Vec<T>>,
+    size: usize,
+}
+
+impl<T> RangeTree2D<T>
+where
+    T: algebra::Group + std::marker::Copy,
+{
+    fn new(points: &Vec<(i64, i64)>) -> Self {
+        let mut xs: Vec<i64> = points.iter().map(|p| p.0).collect();
+        xs.sort();
+        xs.dedup();
+        let mut size = 1;
+        while size < xs.len() {
+            size <<= 1;
+        }
+        let mut ys = vec![Vec::new(); 2 * size];
+        for &(x, y) in points {
+            let i = xs.binary_search(&x).unwrap();
+            ys[size + i].push(y);
+        }
+        for v in (1..2 * size).rev() {
+            if v < size {
+                let mut merged = Vec::with_capacity(ys[2 * v].len() + ys[2 * v + 1].len());
+                merged.extend_from_slice(&ys[2 * v]);
+                merged.extend_from_slice(&ys[2 * v + 1]);
+                ys[v] = merged;
+            }
+            ys[v].sort();
+            ys[v].dedup();
+        }
+        let bit = ys.iter().map(|y| vec![T::zero(); y.len() + 1]).collect();
+        RangeTree2D {
+            xs: xs,
+            ys: ys,
+            bit: bit,
+            size: size,
+        }
+    }
+
+    // (x, y) は new に渡した点でなければならない
+    fn add(&mut self, x: i64, y: i64, w: T) {
+        let i = self.xs.binary_search(&x).expect("RangeTree2D: unknown point");
+        let mut v = self.size + i;
+        while v > 0 {
+            let j = self.ys[v].binary_search(&y).expect("RangeTree2D: unknown point");
+            let bit = &mut self.bit[v];
+            let mut k = j + 1;
+            while k < bit.len() {
+                bit[k] = bit[k] + w;
+                k += k & k.wrapping_neg();
+            }
+            v >>= 1;
+        }
+    }
+
+    // ノード v のうち y < ub のものの和
+    fn prefix(&self, v: usize, ub: i64) -> T {
+        let mut k = match self.ys[v].binary_search(&ub) {
+            Ok(k) => k,
+            Err(k) => k,
+        };
+        let mut res = T::zero();
+        while k > 0 {
+            res = res + self.bit[v][k];
+            k -= k & k.wrapping_neg();
+        }
+        res
+    }
+
+    // [lx, rx) x [ly, ry) に含まれる点の重みの和
+    fn query(&self, lx: i64, ly: i64, rx: i64, ry: i64) -> T {
+        let lower = |x: i64| match self.xs.binary_search(&x) {
+            Ok(i) => i,
+            Err(i) => i,
+        };
+        let mut l = lower(lx) + self.size;
+        let mut r = lower(rx) + self.size;
+        let mut res = T::zero();
+        while l < r {
+            if l & 1 == 1 {
+                res = res + self.prefix(l, ry) - self.prefix(l, ly);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                res = res + self.prefix(r, ry) - self.prefix(r, ly);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        res
+    }
+}
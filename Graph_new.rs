@@ -124,6 +124,139 @@ where
     }
 }
 
+// 閉路の頂点を辺をたどる順に並べたもの
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Cycle(Vec<usize>);
+
+trait GraphTopologicalSort: Graph {
+    // 閉路があればその一つを返す
+    fn topological_sort(&self) -> Result<Vec<usize>, Cycle>;
+    // 辞書順最小のトポロジカル順
+    fn lexicographic_topological_sort(&self) -> Result<Vec<usize>, Cycle>;
+    // res[v]: v で終わる最長路の長さ (重みの和)
+    fn longest_path(&self) -> Result<Vec<Self::Cost>, Cycle>
+    where
+        Self::Cost: Ord + Add<Self::Cost, Output = Self::Cost> + Zero + Copy;
+    // res[v]: from から v へのパスの本数 mod modulo
+    fn count_paths(&self, from: usize, modulo: u64) -> Result<Vec<u64>, Cycle>;
+}
+
+impl<T: Graph> GraphTopologicalSort for T {
+    fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        let n = self.len();
+        let mut deg = vec![0; n];
+        for from in 0..n {
+            for e in self.get(from) {
+                deg[e.to] += 1;
+            }
+        }
+        let mut res = Vec::with_capacity(n);
+        let mut stack: Vec<usize> = (0..n).filter(|&v| deg[v] == 0).collect();
+        while let Some(p) = stack.pop() {
+            res.push(p);
+            for e in self.get(p) {
+                deg[e.to] -= 1;
+                if deg[e.to] == 0 {
+                    stack.push(e.to);
+                }
+            }
+        }
+        if res.len() == n {
+            Ok(res)
+        } else {
+            Err(find_cycle(self, &deg))
+        }
+    }
+
+    fn lexicographic_topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        use std::cmp::Reverse;
+        let n = self.len();
+        let mut deg = vec![0; n];
+        for from in 0..n {
+            for e in self.get(from) {
+                deg[e.to] += 1;
+            }
+        }
+        let mut res = Vec::with_capacity(n);
+        let mut heap: std::collections::BinaryHeap<_> =
+            (0..n).filter(|&v| deg[v] == 0).map(Reverse).collect();
+        while let Some(Reverse(p)) = heap.pop() {
+            res.push(p);
+            for e in self.get(p) {
+                deg[e.to] -= 1;
+                if deg[e.to] == 0 {
+                    heap.push(Reverse(e.to));
+                }
+            }
+        }
+        if res.len() == n {
+            Ok(res)
+        } else {
+            Err(find_cycle(self, &deg))
+        }
+    }
+
+    fn longest_path(&self) -> Result<Vec<Self::Cost>, Cycle>
+    where
+        Self::Cost: Ord + Add<Self::Cost, Output = Self::Cost> + Zero + Copy,
+    {
+        let order = self.topological_sort()?;
+        let mut dist = vec![Self::Cost::zero(); self.len()];
+        for p in order {
+            for e in self.get(p) {
+                if dist[e.to] < dist[p] + e.cost {
+                    dist[e.to] = dist[p] + e.cost;
+                }
+            }
+        }
+        Ok(dist)
+    }
+
+    fn count_paths(&self, from: usize, modulo: u64) -> Result<Vec<u64>, Cycle> {
+        let order = self.topological_sort()?;
+        let mut cnt = vec![0; self.len()];
+        cnt[from] = 1 % modulo;
+        for p in order {
+            if cnt[p] == 0 {
+                continue;
+            }
+            for e in self.get(p) {
+                cnt[e.to] = (cnt[e.to] + cnt[p]) % modulo;
+            }
+        }
+        Ok(cnt)
+    }
+}
+
+// 入次数を消しきれなかった頂点 (deg[v] > 0) からなる部分に必ず閉路がある
+fn find_cycle<G: Graph + ?Sized>(g: &G, deg: &Vec<usize>) -> Cycle {
+    let n = g.len();
+    let mut pre = vec![n; n];
+    for from in 0..n {
+        if deg[from] == 0 {
+            continue;
+        }
+        for e in g.get(from) {
+            pre[e.to] = from;
+        }
+    }
+    // 残った頂点には残った頂点からの辺が必ず入ってくるので, 逆にたどればいつか同じ頂点に戻る
+    let mut v = (0..n).find(|&v| deg[v] > 0).unwrap();
+    let mut visited = vec![false; n];
+    while !visited[v] {
+        visited[v] = true;
+        v = pre[v];
+    }
+    let mut res = vec![v];
+    let mut p = pre[v];
+    while p != v {
+        res.push(p);
+        p = pre[p];
+    }
+    res.reverse();
+    Cycle(res)
+}
+
 // --------------------
 
 trait Zero {
@@ -146,16 +279,90 @@ impl Max for i64 {
     }
 }
 
+impl Max for usize {
+    fn max_value() -> usize {
+        std::usize::MAX
+    }
+}
+
+trait CheckedAdd: Sized {
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+impl CheckedAdd for i64 {
+    fn checked_add(&self, other: &i64) -> Option<i64> {
+        i64::checked_add(*self, *other)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Distance<Cost> {
+    Unreachable,
+    Finite(Cost),
+    // 到達可能な負閉路を経由できる
+    NegativeInfinity,
+}
+
+// dist[v] == max_value() なら到達不能
+#[derive(Clone, Debug)]
+struct ShortestPath<Cost> {
+    dist: Vec<Cost>,
+    pre: Vec<Option<usize>>,
+}
+
+impl<Cost> ShortestPath<Cost>
+where
+    Cost: Max + PartialEq + Copy,
+{
+    fn dist(&self, v: usize) -> Option<Cost> {
+        if self.dist[v] == Cost::max_value() {
+            None
+        } else {
+            Some(self.dist[v])
+        }
+    }
+
+    // 始点から v までの頂点列 (始点と v を含む)
+    fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        if self.dist(v).is_none() {
+            return None;
+        }
+        let mut res = vec![v];
+        let mut p = v;
+        while let Some(q) = self.pre[p] {
+            res.push(q);
+            p = q;
+        }
+        res.reverse();
+        Some(res)
+    }
+}
+
 trait GraphHavingOrderedCost: Graph
 where
     Self::Cost: Ord + Add<Self::Cost, Output = Self::Cost> + Zero + Max + Copy,
 {
     fn get_shortest_path(&self, from: usize) -> (Vec<Self::Cost>, Vec<usize>);
-    fn bellmanford(&self, from: usize) -> (Vec<Self::Cost>, Vec<bool>);
+    fn dijkstra(&self, from: usize) -> ShortestPath<Self::Cost>;
+    fn dijkstra_multi(&self, sources: &[usize]) -> ShortestPath<Self::Cost>;
+    // 返り値の .1[v] は v の距離が負の無限大になるかどうか
+    fn bellmanford(&self, from: usize) -> (Vec<Self::Cost>, Vec<bool>)
+    where
+        Self::Cost: CheckedAdd;
+    fn bellmanford_distance(&self, from: usize) -> Vec<Distance<Self::Cost>>
+    where
+        Self::Cost: CheckedAdd;
+    fn spfa(&self, from: usize) -> Vec<Distance<Self::Cost>>
+    where
+        Self::Cost: CheckedAdd;
+    // 負閉路をひとつ, 辺をたどる順の頂点列で返す
+    fn find_negative_cycle(&self) -> Option<Vec<usize>>
+    where
+        Self::Cost: CheckedAdd;
     fn get_shortest_path_graph(&self, from: usize, dist: &Vec<Self::Cost>) -> AdjList<Self::Cost>;
 }
 
-use std::ops::Add;
+use std::ops::{Add, Sub};
 impl<T> GraphHavingOrderedCost for T
 where
     T: Graph,
@@ -164,16 +371,35 @@ where
 {
     fn get_shortest_path(&self, from: usize) -> (Vec<Self::Cost>, Vec<usize>) {
         let n = self.len();
+        let res = self.dijkstra(from);
+        let pre = res.pre.iter().map(|p| p.unwrap_or(n)).collect();
+        (res.dist, pre)
+    }
+
+    fn dijkstra(&self, from: usize) -> ShortestPath<Self::Cost> {
+        self.dijkstra_multi(&[from])
+    }
+
+    fn dijkstra_multi(&self, sources: &[usize]) -> ShortestPath<Self::Cost> {
+        let n = self.len();
 
         let mut dist = vec![Self::Cost::max_value(); n];
-        let mut pre = vec![n as usize; n];
+        let mut pre = vec![None; n];
         let mut heap = std::collections::BinaryHeap::new();
-        heap.push(Edge {
-            to: from,
-            cost: Self::Cost::zero(),
-        });
-        dist[from] = Self::Cost::zero();
+        for &s in sources {
+            if dist[s] == Self::Cost::zero() {
+                continue;
+            }
+            heap.push(Edge {
+                to: s,
+                cost: Self::Cost::zero(),
+            });
+            dist[s] = Self::Cost::zero();
+        }
         while let Some(p) = heap.pop() {
+            if p.cost > dist[p.to] {
+                continue;
+            }
             for e in self.get(p.to) {
                 if p.cost + e.cost >= dist[e.to] {
                     continue;
@@ -183,32 +409,185 @@ where
                     cost: p.cost + e.cost,
                 });
                 dist[e.to] = p.cost + e.cost;
-                pre[e.to] = p.to;
+                pre[e.to] = Some(p.to);
             }
         }
-        (dist, pre)
+        ShortestPath {
+            dist: dist,
+            pre: pre,
+        }
     }
 
-    fn bellmanford(&self, from: usize) -> (Vec<Self::Cost>, Vec<bool>) {
+    fn bellmanford(&self, from: usize) -> (Vec<Self::Cost>, Vec<bool>)
+    where
+        Self::Cost: CheckedAdd,
+    {
         let n = self.len();
         let mut dist = vec![Self::Cost::max_value(); n];
-        let mut updated = vec![true; n];
         dist[from] = Self::Cost::zero();
-        for _ in 0..n+1 {
-            updated = vec![false; n];
+        for _ in 1..n {
+            let mut updated = false;
+            for from in 0..n {
+                if dist[from] == Self::Cost::max_value() {
+                    continue;
+                }
+                for e in self.get(from) {
+                    if let Some(d) = dist[from].checked_add(&e.cost) {
+                        if dist[e.to] > d {
+                            dist[e.to] = d;
+                            updated = true;
+                        }
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
+        // さらに n 回回して, 更新された頂点とそこから到達できる頂点を -inf とする
+        let mut neg = vec![false; n];
+        for _ in 0..n {
+            let mut updated = false;
             for from in 0..n {
                 if dist[from] == Self::Cost::max_value() {
                     continue;
                 }
                 for e in self.get(from) {
-                    if dist[e.to] > dist[from]+e.cost {
-                        dist[e.to] = dist[from]+e.cost;
-                        updated[e.to] = true;
+                    if neg[from] {
+                        if !neg[e.to] {
+                            neg[e.to] = true;
+                            updated = true;
+                        }
+                        continue;
+                    }
+                    if let Some(d) = dist[from].checked_add(&e.cost) {
+                        if dist[e.to] > d {
+                            dist[e.to] = d;
+                            if !neg[e.to] {
+                                neg[e.to] = true;
+                                updated = true;
+                            }
+                        }
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
+        (dist, neg)
+    }
+
+    fn bellmanford_distance(&self, from: usize) -> Vec<Distance<Self::Cost>>
+    where
+        Self::Cost: CheckedAdd,
+    {
+        let (dist, neg) = self.bellmanford(from);
+        dist.into_iter()
+            .zip(neg)
+            .map(|(d, f)| if f {
+                Distance::NegativeInfinity
+            } else if d == Self::Cost::max_value() {
+                Distance::Unreachable
+            } else {
+                Distance::Finite(d)
+            })
+            .collect()
+    }
+
+    fn spfa(&self, from: usize) -> Vec<Distance<Self::Cost>>
+    where
+        Self::Cost: CheckedAdd,
+    {
+        let n = self.len();
+        let mut dist = vec![Self::Cost::max_value(); n];
+        let mut cnt = vec![0; n];
+        let mut in_queue = vec![false; n];
+        let mut neg = vec![false; n];
+        let mut queue = std::collections::VecDeque::new();
+        dist[from] = Self::Cost::zero();
+        queue.push_back(from);
+        in_queue[from] = true;
+        while let Some(p) = queue.pop_front() {
+            in_queue[p] = false;
+            if neg[p] {
+                continue;
+            }
+            for e in self.get(p) {
+                if let Some(d) = dist[p].checked_add(&e.cost) {
+                    if dist[e.to] > d {
+                        dist[e.to] = d;
+                        // 最短路の辺数が n 以上なら負閉路を通っている
+                        cnt[e.to] = cnt[p] + 1;
+                        if cnt[e.to] >= n {
+                            neg[e.to] = true;
+                        }
+                        if !in_queue[e.to] {
+                            in_queue[e.to] = true;
+                            queue.push_back(e.to);
+                        }
                     }
                 }
             }
         }
-        (dist, updated)
+        let mut stack: Vec<usize> = (0..n).filter(|&v| neg[v]).collect();
+        while let Some(p) = stack.pop() {
+            for e in self.get(p) {
+                if !neg[e.to] {
+                    neg[e.to] = true;
+                    stack.push(e.to);
+                }
+            }
+        }
+        (0..n)
+            .map(|v| if neg[v] {
+                Distance::NegativeInfinity
+            } else if dist[v] == Self::Cost::max_value() {
+                Distance::Unreachable
+            } else {
+                Distance::Finite(dist[v])
+            })
+            .collect()
+    }
+
+    fn find_negative_cycle(&self) -> Option<Vec<usize>>
+    where
+        Self::Cost: CheckedAdd,
+    {
+        let n = self.len();
+        // 全頂点を距離 0 の始点とする
+        let mut dist = vec![Self::Cost::zero(); n];
+        let mut pre = vec![n; n];
+        let mut last = n;
+        for _ in 0..n {
+            last = n;
+            for from in 0..n {
+                for e in self.get(from) {
+                    if let Some(d) = dist[from].checked_add(&e.cost) {
+                        if dist[e.to] > d {
+                            dist[e.to] = d;
+                            pre[e.to] = from;
+                            last = e.to;
+                        }
+                    }
+                }
+            }
+            if last == n {
+                return None;
+            }
+        }
+        let mut v = last;
+        for _ in 0..n {
+            v = pre[v];
+        }
+        let mut res = vec![v];
+        let mut p = pre[v];
+        while p != v {
+            res.push(p);
+            p = pre[p];
+        }
+        res.reverse();
+        Some(res)
     }
 
     fn get_shortest_path_graph(&self, from: usize, dist: &Vec<Self::Cost>) -> AdjList<Self::Cost> {
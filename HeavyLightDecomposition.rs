@@ -0,0 +1,268 @@
+// Graph_new.rs, segtree.rs, LazySegmentTree.rs と一緒に使う (Monoid は一つにまとめる)
+// 頂点 v の値はセグ木の index[v] 番目に置く
+// edge = true のときは辺 (par[v], v) の値を index[v] 番目に置いたものとして LCA を除く
+#[derive(Clone, Debug)]
+struct HeavyLightDecomposition {
+    // 根の親は usize::MAX
+    par: Vec<usize>,
+    depth: Vec<usize>,
+    // v の属する heavy path の一番上の頂点
+    head: Vec<usize>,
+    index: Vec<usize>,
+    // index の逆
+    vertex: Vec<usize>,
+    size: Vec<usize>,
+}
+
+// 向きのある積. down は index の昇順 (根から葉の向き), up はその逆順に掛けたもの
+#[derive(Clone, Debug)]
+struct Directed<T> {
+    down: T,
+    up: T,
+}
+
+impl<T: Monoid + Clone> Directed<T> {
+    fn new(x: T) -> Directed<T> {
+        Directed {
+            down: x.clone(),
+            up: x,
+        }
+    }
+}
+
+impl<T: Monoid> Monoid for Directed<T> {
+    fn unity() -> Self {
+        Directed {
+            down: T::unity(),
+            up: T::unity(),
+        }
+    }
+
+    fn op(&self, other: &Self) -> Self {
+        Directed {
+            down: self.down.op(&other.down),
+            up: other.up.op(&self.up),
+        }
+    }
+}
+
+impl HeavyLightDecomposition {
+    fn new<G: Graph>(g: &G, root: usize) -> HeavyLightDecomposition {
+        let n = g.len();
+        let unvisited = std::usize::MAX;
+        let mut par = vec![unvisited; n];
+        let mut depth = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for e in g.get(v) {
+                if e.to != par[v] {
+                    par[e.to] = v;
+                    depth[e.to] = depth[v] + 1;
+                    stack.push(e.to);
+                }
+            }
+        }
+        let mut size = vec![1; n];
+        let mut heavy = vec![unvisited; n];
+        for &v in order.iter().rev() {
+            let p = par[v];
+            if p == unvisited {
+                continue;
+            }
+            size[p] += size[v];
+            if heavy[p] == unvisited || size[heavy[p]] < size[v] {
+                heavy[p] = v;
+            }
+        }
+
+        // heavy な子を最後に積んで, 次に取り出されるようにする
+        let mut head = vec![root; n];
+        let mut index = vec![0; n];
+        let mut vertex = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            index[v] = vertex.len();
+            vertex.push(v);
+            for e in g.get(v) {
+                if e.to != par[v] && e.to != heavy[v] {
+                    head[e.to] = e.to;
+                    stack.push(e.to);
+                }
+            }
+            if heavy[v] != unvisited {
+                head[heavy[v]] = head[v];
+                stack.push(heavy[v]);
+            }
+        }
+        HeavyLightDecomposition {
+            par: par,
+            depth: depth,
+            head: head,
+            index: index,
+            vertex: vertex,
+            size: size,
+        }
+    }
+
+    fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = (u, v);
+        while self.head[u] != self.head[v] {
+            if self.index[self.head[u]] > self.index[self.head[v]] {
+                u = self.par[self.head[u]];
+            } else {
+                v = self.par[self.head[v]];
+            }
+        }
+        if self.depth[u] < self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    // u から v へのパスを区間 [l, r) に分ける
+    // 1 つ目は u から LCA へ上る区間 (区間の中は index の降順にたどる),
+    // 2 つ目は LCA から v へ下る区間 (区間の中は index の昇順にたどる) で, どちらもたどる順に並ぶ
+    fn path(&self, u: usize, v: usize, edge: bool) -> (Vec<(usize, usize)>, Vec<(usize, usize)>) {
+        let (mut u, mut v) = (u, v);
+        let mut up = Vec::new();
+        let mut down = Vec::new();
+        while self.head[u] != self.head[v] {
+            if self.index[self.head[u]] > self.index[self.head[v]] {
+                up.push((self.index[self.head[u]], self.index[u] + 1));
+                u = self.par[self.head[u]];
+            } else {
+                down.push((self.index[self.head[v]], self.index[v] + 1));
+                v = self.par[self.head[v]];
+            }
+        }
+        let skip = if edge { 1 } else { 0 };
+        if self.index[u] >= self.index[v] {
+            if self.index[v] + skip <= self.index[u] {
+                up.push((self.index[v] + skip, self.index[u] + 1));
+            }
+        } else {
+            down.push((self.index[u] + skip, self.index[v] + 1));
+        }
+        down.reverse();
+        (up, down)
+    }
+
+    // v の部分木に対応する区間 [l, r)
+    fn subtree(&self, v: usize, edge: bool) -> (usize, usize) {
+        let skip = if edge { 1 } else { 0 };
+        (self.index[v] + skip, self.index[v] + self.size[v])
+    }
+
+    // 可換なモノイドでのパス上の積
+    fn path_fold<T, Q>(&self, u: usize, v: usize, edge: bool, mut query: Q) -> T
+    where
+        T: Monoid,
+        Q: FnMut(usize, usize) -> T,
+    {
+        let (up, down) = self.path(u, v, edge);
+        let mut res = T::unity();
+        for (l, r) in up.into_iter().chain(down) {
+            res = res.op(&query(l, r));
+        }
+        res
+    }
+
+    // 非可換なモノイドで u から v の順に掛けた積
+    fn path_fold_directed<T, Q>(&self, u: usize, v: usize, edge: bool, mut query: Q) -> T
+    where
+        T: Monoid,
+        Q: FnMut(usize, usize) -> Directed<T>,
+    {
+        let (up, down) = self.path(u, v, edge);
+        let mut res = T::unity();
+        for (l, r) in up {
+            res = res.op(&query(l, r).up);
+        }
+        for (l, r) in down {
+            res = res.op(&query(l, r).down);
+        }
+        res
+    }
+
+    fn path_fold_segtree<T>(&self, u: usize, v: usize, edge: bool, seg: &SegmentTree<T>) -> T
+    where
+        T: Monoid + Clone,
+    {
+        self.path_fold(u, v, edge, |l, r| seg.query(l, r))
+    }
+
+    // seg の各要素は Directed::new(x) で入れておく
+    fn path_fold_directed_segtree<T>(
+        &self,
+        u: usize,
+        v: usize,
+        edge: bool,
+        seg: &SegmentTree<Directed<T>>,
+    ) -> T
+    where
+        T: Monoid + Clone,
+    {
+        self.path_fold_directed(u, v, edge, |l, r| seg.query(l, r))
+    }
+
+    fn path_fold_lazy<T, U, F>(
+        &self,
+        u: usize,
+        v: usize,
+        edge: bool,
+        seg: &mut LazySegmentTree<T, U, F>,
+    ) -> T
+    where
+        T: Monoid + Clone + std::fmt::Debug,
+        U: Monoid + Clone + std::fmt::Debug,
+        F: FnMut(&mut T, U, usize, usize),
+    {
+        self.path_fold(u, v, edge, |l, r| seg.query(l, r))
+    }
+
+    fn path_update_lazy<T, U, F>(
+        &self,
+        u: usize,
+        v: usize,
+        edge: bool,
+        seg: &mut LazySegmentTree<T, U, F>,
+        x: U,
+    ) where
+        T: Monoid + Clone + std::fmt::Debug,
+        U: Monoid + Clone + std::fmt::Debug,
+        F: FnMut(&mut T, U, usize, usize),
+    {
+        let (up, down) = self.path(u, v, edge);
+        for (l, r) in up.into_iter().chain(down) {
+            seg.range_update(l, r, x.clone());
+        }
+    }
+
+    fn subtree_fold_lazy<T, U, F>(&self, v: usize, edge: bool, seg: &mut LazySegmentTree<T, U, F>) -> T
+    where
+        T: Monoid + Clone + std::fmt::Debug,
+        U: Monoid + Clone + std::fmt::Debug,
+        F: FnMut(&mut T, U, usize, usize),
+    {
+        let (l, r) = self.subtree(v, edge);
+        seg.query(l, r)
+    }
+
+    fn subtree_update_lazy<T, U, F>(
+        &self,
+        v: usize,
+        edge: bool,
+        seg: &mut LazySegmentTree<T, U, F>,
+        x: U,
+    ) where
+        T: Monoid + Clone + std::fmt::Debug,
+        U: Monoid + Clone + std::fmt::Debug,
+        F: FnMut(&mut T, U, usize, usize),
+    {
+        let (l, r) = self.subtree(v, edge);
+        seg.range_update(l, r, x);
+    }
+}
@@ -0,0 +1,158 @@
+// O(n) 前計算 / O(1) クエリの RMQ
+// 64 要素ごとのブロックに分け, ブロック内はスタックのビットマスク, ブロックの最小値どうしは sparse table で求める
+#[derive(Clone, Debug)]
+struct LinearRMQ<T> {
+    a: Vec<T>,
+    // mask[i]: i を右端としたときの単調スタックに残っている位置 (ブロック先頭からの相対位置)
+    mask: Vec<u64>,
+    // table[k][b]: ブロック b..b+2^k の中の最小値の位置
+    table: Vec<Vec<usize>>,
+}
+
+impl<T: PartialOrd> LinearRMQ<T> {
+    fn new(a: Vec<T>) -> LinearRMQ<T> {
+        let n = a.len();
+        let mut mask = vec![0u64; n];
+        let mut stack: u64 = 0;
+        for i in 0..n {
+            if i % 64 == 0 {
+                stack = 0;
+            }
+            let bs = i - i % 64;
+            while stack != 0 {
+                let top = 63 - stack.leading_zeros() as usize;
+                if a[bs + top] > a[i] {
+                    stack ^= 1 << top;
+                } else {
+                    break;
+                }
+            }
+            stack |= 1 << (i % 64);
+            mask[i] = stack;
+        }
+
+        let m = (n + 63) / 64;
+        let mut first: Vec<usize> = Vec::with_capacity(m);
+        for b in 0..m {
+            let last = std::cmp::min(n, (b + 1) * 64) - 1;
+            first.push(b * 64 + mask[last].trailing_zeros() as usize);
+        }
+        let mut table = vec![first];
+        let mut k = 1;
+        while 2 * k <= m {
+            let next = {
+                let prev = table.last().unwrap();
+                (0..m + 1 - 2 * k)
+                    .map(|b| {
+                        let (x, y) = (prev[b], prev[b + k]);
+                        if a[y] < a[x] { y } else { x }
+                    })
+                    .collect()
+            };
+            table.push(next);
+            k *= 2;
+        }
+        LinearRMQ {
+            a: a,
+            mask: mask,
+            table: table,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    fn better(&self, x: usize, y: usize) -> usize {
+        if self.a[y] < self.a[x] { y } else { x }
+    }
+
+    // [l, r] (同じブロック内) の最小値の位置
+    fn in_block(&self, l: usize, r: usize) -> usize {
+        let bs = l - l % 64;
+        let m = self.mask[r] & !((1u64 << (l % 64)) - 1);
+        bs + m.trailing_zeros() as usize
+    }
+
+    // [l, r) の最小値の位置 (同じ値なら最も左)
+    fn query(&self, l: usize, r: usize) -> usize {
+        assert!(l < r && r <= self.len());
+        let r = r - 1;
+        let (lb, rb) = (l / 64, r / 64);
+        if lb == rb {
+            return self.in_block(l, r);
+        }
+        let mut res = self.in_block(l, lb * 64 + 63);
+        if lb + 1 < rb {
+            let (x, y) = (lb + 1, rb);
+            let k = (0usize.leading_zeros() - (y - x).leading_zeros() - 1) as usize;
+            res = self.better(res, self.table[k][x]);
+            res = self.better(res, self.table[k][y - (1 << k)]);
+        }
+        self.better(res, self.in_block(rb * 64, r))
+    }
+
+    fn min(&self, l: usize, r: usize) -> &T {
+        &self.a[self.query(l, r)]
+    }
+}
+
+// Euler tour 上の深さの RMQ による LCA
+#[derive(Clone, Debug)]
+struct EulerTourLCA {
+    // tour[i]: i 番目に訪れた頂点
+    tour: Vec<usize>,
+    // first[v]: v を初めて訪れた時刻
+    first: Vec<usize>,
+    rmq: LinearRMQ<usize>,
+}
+
+impl EulerTourLCA {
+    fn new<G: Graph>(g: &G, root: usize) -> EulerTourLCA {
+        let n = g.len();
+        let mut tour = Vec::with_capacity(2 * n);
+        let mut depth = Vec::with_capacity(2 * n);
+        let mut first = vec![std::usize::MAX; n];
+        let mut stack = vec![(root, std::usize::MAX, 0, g.get(root))];
+        first[root] = 0;
+        tour.push(root);
+        depth.push(0);
+        while let Some((v, par, d, mut iter)) = stack.pop() {
+            match iter.next() {
+                Some(e) => {
+                    stack.push((v, par, d, iter));
+                    if e.to == par {
+                        continue;
+                    }
+                    first[e.to] = tour.len();
+                    tour.push(e.to);
+                    depth.push(d + 1);
+                    stack.push((e.to, v, d + 1, g.get(e.to)));
+                }
+                None => {
+                    if let Some(&(p, _, pd, _)) = stack.last() {
+                        tour.push(p);
+                        depth.push(pd);
+                    }
+                }
+            }
+        }
+        EulerTourLCA {
+            tour: tour,
+            first: first,
+            rmq: LinearRMQ::new(depth),
+        }
+    }
+
+    fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut a, mut b) = (self.first[u], self.first[v]);
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.tour[self.rmq.query(a, b + 1)]
+    }
+
+    fn depth(&self, v: usize) -> usize {
+        self.rmq.a[self.first[v]]
+    }
+}
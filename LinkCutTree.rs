@@ -0,0 +1,240 @@
+trait Monoid {
+    fn unity() -> Self;
+    fn op(&self, other: &Self) -> Self;
+}
+
+// 頂点に値を持つ森. 非可換なモノイドでもよい
+// 各 splay 木は heavy path を表し, 中順が根に近い方から遠い方の順になる
+#[derive(Clone, Debug)]
+struct LinkCutTree<T> {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    // splay 木の親, splay 木の根なら path の親 (なければ usize::MAX)
+    par: Vec<usize>,
+    // 子の左右を反転する必要があるか (自身は反転済み)
+    rev: Vec<bool>,
+    val: Vec<T>,
+    // 部分木を中順に掛けたものと, その逆順
+    sum: Vec<T>,
+    rsum: Vec<T>,
+}
+
+impl<T: Monoid + Clone> LinkCutTree<T> {
+    const NIL: usize = std::usize::MAX;
+
+    fn new(n: usize) -> LinkCutTree<T> {
+        LinkCutTree::from_vec(vec![T::unity(); n])
+    }
+
+    fn from_vec(v: Vec<T>) -> LinkCutTree<T> {
+        let n = v.len();
+        LinkCutTree {
+            left: vec![Self::NIL; n],
+            right: vec![Self::NIL; n],
+            par: vec![Self::NIL; n],
+            rev: vec![false; n],
+            sum: v.clone(),
+            rsum: v.clone(),
+            val: v,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.val.len()
+    }
+
+    fn is_root(&self, x: usize) -> bool {
+        let p = self.par[x];
+        p == Self::NIL || (self.left[p] != x && self.right[p] != x)
+    }
+
+    fn toggle(&mut self, x: usize) {
+        if x == Self::NIL {
+            return;
+        }
+        let (l, r) = (self.left[x], self.right[x]);
+        self.left[x] = r;
+        self.right[x] = l;
+        std::mem::swap(&mut self.sum[x], &mut self.rsum[x]);
+        self.rev[x] ^= true;
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.rev[x] {
+            let (l, r) = (self.left[x], self.right[x]);
+            self.toggle(l);
+            self.toggle(r);
+            self.rev[x] = false;
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let (l, r) = (self.left[x], self.right[x]);
+        let mut sum = self.val[x].clone();
+        let mut rsum = self.val[x].clone();
+        if l != Self::NIL {
+            sum = self.sum[l].op(&sum);
+            rsum = rsum.op(&self.rsum[l]);
+        }
+        if r != Self::NIL {
+            sum = sum.op(&self.sum[r]);
+            rsum = self.rsum[r].op(&rsum);
+        }
+        self.sum[x] = sum;
+        self.rsum[x] = rsum;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.par[x];
+        let g = self.par[p];
+        if !self.is_root(p) {
+            if self.left[g] == p {
+                self.left[g] = x;
+            } else {
+                self.right[g] = x;
+            }
+        }
+        self.par[x] = g;
+        if self.left[p] == x {
+            let b = self.right[x];
+            self.left[p] = b;
+            if b != Self::NIL {
+                self.par[b] = p;
+            }
+            self.right[x] = p;
+        } else {
+            let b = self.left[x];
+            self.right[p] = b;
+            if b != Self::NIL {
+                self.par[b] = p;
+            }
+            self.left[x] = p;
+        }
+        self.par[p] = x;
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // 上から順に遅延している反転を降ろす
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_root(y) {
+            y = self.par[y];
+            path.push(y);
+        }
+        for &y in path.iter().rev() {
+            self.push(y);
+        }
+        while !self.is_root(x) {
+            let p = self.par[x];
+            if !self.is_root(p) {
+                let g = self.par[p];
+                if (self.left[g] == p) == (self.left[p] == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    // 根から x までを 1 つの splay 木にして x をその根にする. 最後に path を切り替えた頂点を返す
+    fn expose(&mut self, x: usize) -> usize {
+        let mut last = Self::NIL;
+        let mut y = x;
+        while y != Self::NIL {
+            self.splay(y);
+            self.right[y] = last;
+            self.update(y);
+            last = y;
+            y = self.par[y];
+        }
+        self.splay(x);
+        last
+    }
+
+    // x を木の根にする
+    fn evert(&mut self, x: usize) {
+        self.expose(x);
+        self.toggle(x);
+    }
+
+    fn find_root(&mut self, x: usize) -> usize {
+        self.expose(x);
+        let mut y = x;
+        loop {
+            self.push(y);
+            if self.left[y] == Self::NIL {
+                break;
+            }
+            y = self.left[y];
+        }
+        self.splay(y);
+        y
+    }
+
+    fn connected(&mut self, u: usize, v: usize) -> bool {
+        u == v || self.find_root(u) == self.find_root(v)
+    }
+
+    // 辺 (u, v) を張る. 既に連結なら何もせず false
+    fn link(&mut self, u: usize, v: usize) -> bool {
+        self.evert(u);
+        if self.find_root(v) == u {
+            return false;
+        }
+        self.par[u] = v;
+        true
+    }
+
+    // 辺 (u, v) を切る. 辺がなければ何もせず false
+    fn cut(&mut self, u: usize, v: usize) -> bool {
+        if u == v {
+            return false;
+        }
+        self.evert(u);
+        self.expose(v);
+        if self.left[v] != u {
+            return false;
+        }
+        self.push(u);
+        if self.right[u] != Self::NIL {
+            return false;
+        }
+        self.left[v] = Self::NIL;
+        self.par[u] = Self::NIL;
+        self.update(v);
+        true
+    }
+
+    // 今の根で見た u と v の LCA. 連結でなければ None
+    fn lca(&mut self, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.expose(u);
+        Some(self.expose(v))
+    }
+
+    fn get(&self, v: usize) -> T {
+        self.val[v].clone()
+    }
+
+    fn set(&mut self, v: usize, x: T) {
+        self.expose(v);
+        self.val[v] = x;
+        self.update(v);
+    }
+
+    // u から v へのパス上の値を u から順に掛けたもの. 連結でなければ None
+    fn path_fold(&mut self, u: usize, v: usize) -> Option<T> {
+        self.evert(u);
+        if self.find_root(v) != u {
+            return None;
+        }
+        self.expose(v);
+        Some(self.sum[v].clone())
+    }
+}
@@ -0,0 +1,143 @@
+// Graph_new.rs と一緒に使う
+// 無向グラフ (add_uedge で作ったもの) を仮定する. 多重辺があってもよい
+#[derive(Clone, Debug)]
+struct LowLink {
+    ord: Vec<usize>,
+    low: Vec<usize>,
+    // DFS 木での親 (根なら usize::MAX)
+    par: Vec<usize>,
+    bridges: Vec<(usize, usize)>,
+    articulation: Vec<usize>,
+    // 二重頂点連結成分ごとの頂点集合 (孤立点も 1 つの成分とする)
+    bcc: Vec<Vec<usize>>,
+}
+
+impl LowLink {
+    fn new<G: Graph>(g: &G) -> LowLink {
+        let n = g.len();
+        let unvisited = std::usize::MAX;
+        let mut ord = vec![unvisited; n];
+        let mut low = vec![0; n];
+        let mut par = vec![unvisited; n];
+        let mut is_art = vec![false; n];
+        let mut bridges = Vec::new();
+        let mut bcc = Vec::new();
+        let mut edge_stack: Vec<(usize, usize)> = Vec::new();
+        let mut time = 0;
+        for s in 0..n {
+            if ord[s] != unvisited {
+                continue;
+            }
+            ord[s] = time;
+            low[s] = time;
+            time += 1;
+            let mut root_children = 0;
+            // (頂点, 親への辺をもう読み飛ばしたか, 隣接辺のイテレータ)
+            let mut call = vec![(s, false, g.get(s))];
+            while let Some((v, mut skipped, mut iter)) = call.pop() {
+                if let Some(e) = iter.next() {
+                    let to = e.to;
+                    if to == par[v] && !skipped {
+                        skipped = true;
+                        call.push((v, skipped, iter));
+                        continue;
+                    }
+                    call.push((v, skipped, iter));
+                    if ord[to] == unvisited {
+                        par[to] = v;
+                        ord[to] = time;
+                        low[to] = time;
+                        time += 1;
+                        edge_stack.push((v, to));
+                        call.push((to, false, g.get(to)));
+                    } else if ord[to] < ord[v] {
+                        low[v] = std::cmp::min(low[v], ord[to]);
+                        edge_stack.push((v, to));
+                    }
+                    continue;
+                }
+                let p = par[v];
+                if p == unvisited {
+                    continue;
+                }
+                low[p] = std::cmp::min(low[p], low[v]);
+                if low[v] > ord[p] {
+                    bridges.push((std::cmp::min(p, v), std::cmp::max(p, v)));
+                }
+                if low[v] >= ord[p] {
+                    if p == s {
+                        root_children += 1;
+                    } else {
+                        is_art[p] = true;
+                    }
+                    let mut comp = Vec::new();
+                    while let Some((a, b)) = edge_stack.pop() {
+                        comp.push(a);
+                        comp.push(b);
+                        if (a, b) == (p, v) {
+                            break;
+                        }
+                    }
+                    comp.sort();
+                    comp.dedup();
+                    bcc.push(comp);
+                }
+            }
+            if root_children >= 2 {
+                is_art[s] = true;
+            }
+            if root_children == 0 {
+                bcc.push(vec![s]);
+            }
+        }
+        LowLink {
+            ord: ord,
+            low: low,
+            par: par,
+            bridges: bridges,
+            articulation: (0..n).filter(|&v| is_art[v]).collect(),
+            bcc: bcc,
+        }
+    }
+
+    fn is_bridge(&self, u: usize, v: usize) -> bool {
+        (self.par[v] == u && self.low[v] > self.ord[u]) || (self.par[u] == v && self.low[u] > self.ord[v])
+    }
+
+    // 二重辺連結成分の番号と成分数
+    fn two_edge_connected_components<G: Graph>(&self, g: &G) -> (Vec<usize>, usize) {
+        let n = g.len();
+        let mut comp = vec![std::usize::MAX; n];
+        let mut num = 0;
+        for s in 0..n {
+            if comp[s] != std::usize::MAX {
+                continue;
+            }
+            comp[s] = num;
+            let mut stack = vec![s];
+            while let Some(v) = stack.pop() {
+                for e in g.get(v) {
+                    if comp[e.to] == std::usize::MAX && !self.is_bridge(v, e.to) {
+                        comp[e.to] = num;
+                        stack.push(e.to);
+                    }
+                }
+            }
+            num += 1;
+        }
+        (comp, num)
+    }
+
+    // block-cut tree
+    // 頂点 0..n は元の頂点, n + i は i 番目の二重頂点連結成分で, 各成分とその頂点を結ぶ
+    fn block_cut_tree(&self) -> AdjList<()> {
+        let n = self.ord.len();
+        let mut res = AdjList::new(n + self.bcc.len());
+        for (i, comp) in self.bcc.iter().enumerate() {
+            for &v in comp {
+                res.add_uedge(n + i, v, ());
+            }
+        }
+        res
+    }
+}
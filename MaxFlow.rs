@@ -0,0 +1,246 @@
+// Graph_new.rs と一緒に使う
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FlowEdge<Cap> {
+    from: usize,
+    to: usize,
+    cap: Cap,
+    flow: Cap,
+}
+
+// 辺 i (偶数) の逆辺は i ^ 1
+#[derive(Clone, Debug)]
+struct FlowNetwork<Cap> {
+    g: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    // 残余容量
+    cap: Vec<Cap>,
+    level: Vec<usize>,
+    iter: Vec<usize>,
+}
+
+impl<Cap> FlowNetwork<Cap>
+where
+    Cap: Ord + Add<Cap, Output = Cap> + Sub<Cap, Output = Cap> + Zero + Max + Copy,
+{
+    fn new(n: usize) -> FlowNetwork<Cap> {
+        FlowNetwork {
+            g: vec![vec![]; n],
+            to: vec![],
+            cap: vec![],
+            level: vec![0; n],
+            iter: vec![0; n],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.g.len()
+    }
+
+    // 辺の番号を返す
+    fn add_edge(&mut self, from: usize, to: usize, cap: Cap) -> usize {
+        let id = self.to.len() / 2;
+        self.g[from].push(2 * id);
+        self.to.push(to);
+        self.cap.push(cap);
+        self.g[to].push(2 * id + 1);
+        self.to.push(from);
+        self.cap.push(Cap::zero());
+        id
+    }
+
+    fn get_edge(&self, id: usize) -> FlowEdge<Cap> {
+        let (e, r) = (2 * id, 2 * id + 1);
+        FlowEdge {
+            from: self.to[r],
+            to: self.to[e],
+            cap: self.cap[e] + self.cap[r],
+            flow: self.cap[r],
+        }
+    }
+
+    fn edges(&self) -> Vec<FlowEdge<Cap>> {
+        (0..self.to.len() / 2).map(|id| self.get_edge(id)).collect()
+    }
+
+    fn bfs(&mut self, s: usize) {
+        let unvisited = std::usize::MAX;
+        self.level = vec![unvisited; self.len()];
+        self.level[s] = 0;
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back(s);
+        while let Some(p) = queue.pop_front() {
+            for &e in &self.g[p] {
+                let to = self.to[e];
+                if self.cap[e] > Cap::zero() && self.level[to] == unvisited {
+                    self.level[to] = self.level[p] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+    }
+
+    fn dfs(&mut self, v: usize, t: usize, f: Cap) -> Cap {
+        if v == t {
+            return f;
+        }
+        while self.iter[v] < self.g[v].len() {
+            let e = self.g[v][self.iter[v]];
+            let to = self.to[e];
+            if self.cap[e] > Cap::zero() && self.level[v] < self.level[to] {
+                let d = self.dfs(to, t, std::cmp::min(f, self.cap[e]));
+                if d > Cap::zero() {
+                    self.cap[e] = self.cap[e] - d;
+                    self.cap[e ^ 1] = self.cap[e ^ 1] + d;
+                    return d;
+                }
+            }
+            self.iter[v] += 1;
+        }
+        Cap::zero()
+    }
+
+    // Dinic. 既に流れているフローに上乗せして流した量を返す
+    fn max_flow(&mut self, s: usize, t: usize) -> Cap {
+        self.max_flow_limit(s, t, Cap::max_value())
+    }
+
+    fn max_flow_limit(&mut self, s: usize, t: usize, limit: Cap) -> Cap {
+        let mut flow = Cap::zero();
+        while flow < limit {
+            self.bfs(s);
+            if self.level[t] == std::usize::MAX {
+                break;
+            }
+            self.iter = vec![0; self.len()];
+            loop {
+                let f = self.dfs(s, t, limit - flow);
+                if f == Cap::zero() {
+                    break;
+                }
+                flow = flow + f;
+                if flow == limit {
+                    break;
+                }
+            }
+        }
+        flow
+    }
+
+    // 最大フローを流した後に呼ぶと, 最小カットの s 側の頂点が true になる
+    fn min_cut(&self, s: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.len()];
+        visited[s] = true;
+        let mut stack = vec![s];
+        while let Some(p) = stack.pop() {
+            for &e in &self.g[p] {
+                let to = self.to[e];
+                if self.cap[e] > Cap::zero() && !visited[to] {
+                    visited[to] = true;
+                    stack.push(to);
+                }
+            }
+        }
+        visited
+    }
+
+    // 辺 id の容量を cap に変える. s-t フローの値が減った量を返す
+    // 容量を増やした場合や減った分を迂回できた場合は 0 で, 続けて max_flow(s, t) を呼べば再び最大になる
+    fn set_capacity(&mut self, id: usize, cap: Cap, s: usize, t: usize) -> Cap {
+        let edge = self.get_edge(id);
+        if edge.flow <= cap {
+            self.cap[2 * id] = cap - edge.flow;
+            return Cap::zero();
+        }
+        let over = edge.flow - cap;
+        self.cap[2 * id] = Cap::zero();
+        self.cap[2 * id + 1] = cap;
+        // from に余った over を, まず to へ迂回させ, 残りは s と t へ押し戻す
+        let rerouted = self.max_flow_limit(edge.from, edge.to, over);
+        let rest = over - rerouted;
+        if rest > Cap::zero() {
+            let back = self.max_flow_limit(edge.from, s, rest);
+            let forth = self.max_flow_limit(t, edge.to, rest);
+            debug_assert!(back == rest && forth == rest);
+        }
+        rest
+    }
+
+    // FIFO push-relabel. 既に流れているフローに上乗せして流した量を返す
+    fn max_flow_push_relabel(&mut self, s: usize, t: usize) -> Cap {
+        let n = self.len();
+        if s == t {
+            return Cap::zero();
+        }
+        let mut excess = vec![Cap::zero(); n];
+        let mut height = vec![n; n];
+        // t からの残余グラフ上の距離で高さを初期化する
+        height[t] = 0;
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back(t);
+        while let Some(p) = queue.pop_front() {
+            for &e in &self.g[p] {
+                let to = self.to[e];
+                if self.cap[e ^ 1] > Cap::zero() && height[to] == n && to != t {
+                    height[to] = height[p] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+        height[s] = n;
+
+        let mut active = std::collections::VecDeque::new();
+        let mut in_queue = vec![false; n];
+        for i in 0..self.g[s].len() {
+            let e = self.g[s][i];
+            let d = self.cap[e];
+            let to = self.to[e];
+            // 自己ループは流しても意味がない
+            if d > Cap::zero() && to != s {
+                self.cap[e] = Cap::zero();
+                self.cap[e ^ 1] = self.cap[e ^ 1] + d;
+                excess[to] = excess[to] + d;
+                if to != t && !in_queue[to] {
+                    in_queue[to] = true;
+                    active.push_back(to);
+                }
+            }
+        }
+        while let Some(v) = active.pop_front() {
+            in_queue[v] = false;
+            while excess[v] > Cap::zero() {
+                let mut min_height = std::usize::MAX;
+                for i in 0..self.g[v].len() {
+                    if excess[v] == Cap::zero() {
+                        break;
+                    }
+                    let e = self.g[v][i];
+                    let to = self.to[e];
+                    if self.cap[e] == Cap::zero() || to == v {
+                        continue;
+                    }
+                    if height[v] == height[to] + 1 {
+                        let d = std::cmp::min(excess[v], self.cap[e]);
+                        self.cap[e] = self.cap[e] - d;
+                        self.cap[e ^ 1] = self.cap[e ^ 1] + d;
+                        excess[v] = excess[v] - d;
+                        excess[to] = excess[to] + d;
+                        if to != s && to != t && !in_queue[to] {
+                            in_queue[to] = true;
+                            active.push_back(to);
+                        }
+                    } else {
+                        min_height = std::cmp::min(min_height, height[to]);
+                    }
+                }
+                if excess[v] > Cap::zero() {
+                    // 押せる辺がないので relabel
+                    if min_height == std::usize::MAX {
+                        break;
+                    }
+                    height[v] = min_height + 1;
+                }
+            }
+        }
+        excess[t]
+    }
+}
@@ -0,0 +1,187 @@
+// Graph_new.rs と一緒に使う
+// 残余グラフを AdjList に起こして GraphHavingOrderedCost の dijkstra / bellmanford を使う
+// AdjList は増加路ごとに作り直さず, 同じものを書き換える
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MinCostFlowEdge<T> {
+    from: usize,
+    to: usize,
+    cap: T,
+    flow: T,
+    cost: T,
+}
+
+// 辺 i (偶数) の逆辺は i ^ 1. 負閉路はないものとする
+#[derive(Clone, Debug)]
+struct MinCostFlow<T> {
+    g: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    cap: Vec<T>,
+    cost: Vec<T>,
+}
+
+impl<T> MinCostFlow<T>
+where
+    T: Ord
+        + Add<T, Output = T>
+        + Sub<T, Output = T>
+        + std::ops::Mul<T, Output = T>
+        + Zero
+        + Max
+        + CheckedAdd
+        + Copy,
+    Edge<T>: Ord,
+{
+    fn new(n: usize) -> MinCostFlow<T> {
+        MinCostFlow {
+            g: vec![vec![]; n],
+            to: vec![],
+            cap: vec![],
+            cost: vec![],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.g.len()
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, cap: T, cost: T) -> usize {
+        let id = self.to.len() / 2;
+        self.g[from].push(2 * id);
+        self.to.push(to);
+        self.cap.push(cap);
+        self.cost.push(cost);
+        self.g[to].push(2 * id + 1);
+        self.to.push(from);
+        self.cap.push(T::zero());
+        self.cost.push(T::zero() - cost);
+        id
+    }
+
+    fn get_edge(&self, id: usize) -> MinCostFlowEdge<T> {
+        let (e, r) = (2 * id, 2 * id + 1);
+        MinCostFlowEdge {
+            from: self.to[r],
+            to: self.to[e],
+            cap: self.cap[e] + self.cap[r],
+            flow: self.cap[r],
+            cost: self.cost[e],
+        }
+    }
+
+    fn edges(&self) -> Vec<MinCostFlowEdge<T>> {
+        (0..self.to.len() / 2).map(|id| self.get_edge(id)).collect()
+    }
+
+    // 容量の残っている辺だけからなるグラフ. 辺のコストは f(from, 辺の番号)
+    fn residual<F: Fn(usize, usize) -> T>(&self, f: F) -> AdjList<T> {
+        let mut res = AdjList::new(self.len());
+        self.residual_into(&mut res, f);
+        res
+    }
+
+    // residual と同じものを, res の確保済みの領域を使い回して作る
+    fn residual_into<F: Fn(usize, usize) -> T>(&self, res: &mut AdjList<T>, f: F) {
+        for from in 0..self.len() {
+            res[from].clear();
+            for &e in &self.g[from] {
+                if self.cap[e] > T::zero() {
+                    res.add_edge(from, self.to[e], f(from, e));
+                }
+            }
+        }
+    }
+
+    // s から t へ limit まで流し, (流量, コスト) を返す
+    fn flow(&mut self, s: usize, t: usize, limit: T) -> (T, T) {
+        *self.slope(s, t, limit).last().unwrap()
+    }
+
+    // 流量とコストの関係の折れ線の頂点を (流量, コスト) で返す. 最初は (0, 0)
+    fn slope(&mut self, s: usize, t: usize, limit: T) -> Vec<(T, T)> {
+        let n = self.len();
+        let has_negative = (0..self.to.len())
+            .any(|e| self.cap[e] > T::zero() && self.cost[e] < T::zero());
+        let mut h = if has_negative {
+            let (dist, neg) = self.residual(|_, e| self.cost[e]).bellmanford(s);
+            debug_assert!(neg.iter().all(|&f| !f));
+            dist.into_iter()
+                .map(|d| if d == T::max_value() { T::zero() } else { d })
+                .collect()
+        } else {
+            vec![T::zero(); n]
+        };
+
+        let mut res = vec![(T::zero(), T::zero())];
+        let mut flow = T::zero();
+        let mut cost = T::zero();
+        let mut prev_unit: Option<T> = None;
+        let mut graph = AdjList::new(n);
+        while flow < limit {
+            self.residual_into(&mut graph, |from, e| self.cost[e] + h[from] - h[self.to[e]]);
+            let sp = graph.dijkstra(s);
+            let path = match sp.path_to(t) {
+                Some(path) => path,
+                None => break,
+            };
+            for v in 0..n {
+                if let Some(d) = sp.dist(v) {
+                    h[v] = h[v] + d;
+                }
+            }
+            // 経路上の各区間で, 被約コストが最短路と一致する辺を選ぶ
+            let mut edges = Vec::with_capacity(path.len() - 1);
+            let mut d = limit - flow;
+            for w in path.windows(2) {
+                let (u, v) = (w[0], w[1]);
+                let e = *self.g[u]
+                    .iter()
+                    .find(|&&e| {
+                        self.to[e] == v && self.cap[e] > T::zero()
+                            && self.cost[e] + h[u] == h[v]
+                    })
+                    .unwrap();
+                d = std::cmp::min(d, self.cap[e]);
+                edges.push(e);
+            }
+            for e in edges {
+                self.cap[e] = self.cap[e] - d;
+                self.cap[e ^ 1] = self.cap[e ^ 1] + d;
+            }
+            let unit = h[t] - h[s];
+            flow = flow + d;
+            cost = cost + d * unit;
+            if prev_unit == Some(unit) {
+                res.pop();
+            }
+            res.push((flow, cost));
+            prev_unit = Some(unit);
+        }
+        res
+    }
+}
+
+// 割当問題: 行 i を相異なる列 assign[i] に割り当ててコストの和を最小化する (行数 <= 列数)
+// (最小コスト, assign) を返す
+fn assignment(cost: &Vec<Vec<i64>>) -> (i64, Vec<usize>) {
+    let n = cost.len();
+    let m = if n == 0 { 0 } else { cost[0].len() };
+    assert!(n <= m);
+    let (s, t) = (n + m, n + m + 1);
+    let mut mcf = MinCostFlow::new(n + m + 2);
+    let mut ids = vec![vec![0; m]; n];
+    for i in 0..n {
+        mcf.add_edge(s, i, 1, 0);
+        for j in 0..m {
+            ids[i][j] = mcf.add_edge(i, n + j, 1, cost[i][j]);
+        }
+    }
+    for j in 0..m {
+        mcf.add_edge(n + j, t, 1, 0);
+    }
+    let (_, total) = mcf.flow(s, t, n as i64);
+    let assign = (0..n)
+        .map(|i| (0..m).find(|&j| mcf.get_edge(ids[i][j]).flow == 1).unwrap())
+        .collect();
+    (total, assign)
+}
@@ -0,0 +1,237 @@
+// Graph_new.rs, UnionFind.rs と一緒に使う
+// 無向グラフ (add_uedge で作ったもの) に対しては最小全域森を, 辺 (from, to, cost) の列とともに返す
+
+trait MinimumSpanningTree: Graph
+where
+    Self::Cost: Ord + Add<Self::Cost, Output = Self::Cost> + Zero + Copy,
+{
+    fn kruskal(&self) -> (Self::Cost, Vec<(usize, usize, Self::Cost)>);
+    fn prim(&self) -> (Self::Cost, Vec<(usize, usize, Self::Cost)>);
+    fn boruvka(&self) -> (Self::Cost, Vec<(usize, usize, Self::Cost)>);
+    // 有向グラフの root を根とする最小全域有向木 (Chu-Liu/Edmonds, O(VE))
+    // root から到達できない頂点があれば None
+    fn min_arborescence(&self, root: usize) -> Option<(Self::Cost, Vec<(usize, usize, Self::Cost)>)>
+    where
+        Self::Cost: Sub<Self::Cost, Output = Self::Cost>;
+}
+
+fn edge_list<G: Graph>(g: &G) -> Vec<(usize, usize, G::Cost)> {
+    let mut res = Vec::new();
+    for from in 0..g.len() {
+        for e in g.get(from) {
+            res.push((from, e.to, e.cost.clone()));
+        }
+    }
+    res
+}
+
+impl<T> MinimumSpanningTree for T
+where
+    T: Graph,
+    T::Cost: Ord + Add<T::Cost, Output = T::Cost> + Zero + Copy,
+    Edge<T::Cost>: Ord,
+{
+    fn kruskal(&self) -> (Self::Cost, Vec<(usize, usize, Self::Cost)>) {
+        let mut edges = edge_list(self);
+        edges.sort_by_key(|e| e.2);
+        let mut uf = UnionFind::new(self.len());
+        let mut total = Self::Cost::zero();
+        let mut res = Vec::new();
+        for (u, v, c) in edges {
+            if uf.same(u, v) {
+                continue;
+            }
+            uf.unite(u, v);
+            total = total + c;
+            res.push((u, v, c));
+        }
+        (total, res)
+    }
+
+    fn prim(&self) -> (Self::Cost, Vec<(usize, usize, Self::Cost)>) {
+        let n = self.len();
+        let mut used = vec![false; n];
+        // key[v]: 木から v への最小の辺のコストと, その辺の木側の端点
+        let mut key: Vec<Option<(Self::Cost, usize)>> = vec![None; n];
+        let mut total = Self::Cost::zero();
+        let mut res = Vec::new();
+        let mut heap = std::collections::BinaryHeap::new();
+        for s in 0..n {
+            if used[s] {
+                continue;
+            }
+            heap.push(Edge {
+                to: s,
+                cost: Self::Cost::zero(),
+            });
+            while let Some(p) = heap.pop() {
+                if used[p.to] {
+                    continue;
+                }
+                used[p.to] = true;
+                if let Some((c, from)) = key[p.to] {
+                    total = total + c;
+                    res.push((from, p.to, c));
+                }
+                for e in self.get(p.to) {
+                    if used[e.to] {
+                        continue;
+                    }
+                    let better = match key[e.to] {
+                        Some((c, _)) => e.cost < c,
+                        None => true,
+                    };
+                    if better {
+                        key[e.to] = Some((e.cost, p.to));
+                        heap.push(e.clone());
+                    }
+                }
+            }
+        }
+        (total, res)
+    }
+
+    fn boruvka(&self) -> (Self::Cost, Vec<(usize, usize, Self::Cost)>) {
+        let n = self.len();
+        let edges = edge_list(self);
+        let mut uf = UnionFind::new(n);
+        let mut total = Self::Cost::zero();
+        let mut res = Vec::new();
+        loop {
+            // 成分ごとに外へ出る最小の辺 (コストが同じなら番号の小さい辺)
+            let mut cheapest: Vec<Option<usize>> = vec![None; n];
+            for (i, &(u, v, c)) in edges.iter().enumerate() {
+                let (a, b) = (uf.find(u), uf.find(v));
+                if a == b {
+                    continue;
+                }
+                for &x in &[a, b] {
+                    let better = match cheapest[x] {
+                        Some(j) => (c, i) < (edges[j].2, j),
+                        None => true,
+                    };
+                    if better {
+                        cheapest[x] = Some(i);
+                    }
+                }
+            }
+            let mut merged = false;
+            for x in 0..n {
+                if let Some(i) = cheapest[x] {
+                    let (u, v, c) = edges[i];
+                    if !uf.same(u, v) {
+                        uf.unite(u, v);
+                        total = total + c;
+                        res.push((u, v, c));
+                        merged = true;
+                    }
+                }
+            }
+            if !merged {
+                break;
+            }
+        }
+        (total, res)
+    }
+
+    fn min_arborescence(&self, root: usize) -> Option<(Self::Cost, Vec<(usize, usize, Self::Cost)>)>
+    where
+        Self::Cost: Sub<Self::Cost, Output = Self::Cost>,
+    {
+        let edges = edge_list(self);
+        let chosen = arborescence_(self.len(), &edges, root)?;
+        let mut total = Self::Cost::zero();
+        let mut res = Vec::with_capacity(chosen.len());
+        for i in chosen {
+            total = total + edges[i].2;
+            res.push(edges[i]);
+        }
+        Some((total, res))
+    }
+}
+
+// 選んだ辺の edges での番号を返す
+fn arborescence_<Cost>(n: usize, edges: &Vec<(usize, usize, Cost)>, root: usize) -> Option<Vec<usize>>
+where
+    Cost: Ord + Sub<Cost, Output = Cost> + Copy,
+{
+    // 各頂点に入る最小の辺
+    let mut min_in: Vec<Option<usize>> = vec![None; n];
+    for (i, &(u, v, c)) in edges.iter().enumerate() {
+        if u == v || v == root {
+            continue;
+        }
+        let better = match min_in[v] {
+            Some(j) => c < edges[j].2,
+            None => true,
+        };
+        if better {
+            min_in[v] = Some(i);
+        }
+    }
+    if (0..n).any(|v| v != root && min_in[v].is_none()) {
+        return None;
+    }
+
+    // min_in の辺がなす閉路を探して縮約する
+    let unvisited = std::usize::MAX;
+    let mut comp = vec![unvisited; n];
+    let mut in_cycle = vec![false; n];
+    let mut num = 0;
+    let mut visited = vec![unvisited; n];
+    for s in 0..n {
+        let mut v = s;
+        while v != root && visited[v] == unvisited && comp[v] == unvisited {
+            visited[v] = s;
+            v = edges[min_in[v].unwrap()].0;
+        }
+        if v != root && visited[v] == s && comp[v] == unvisited {
+            let mut w = v;
+            loop {
+                comp[w] = num;
+                in_cycle[w] = true;
+                w = edges[min_in[w].unwrap()].0;
+                if w == v {
+                    break;
+                }
+            }
+            num += 1;
+        }
+    }
+    if num == 0 {
+        return Some((0..n).filter_map(|v| min_in[v]).collect());
+    }
+    for v in 0..n {
+        if comp[v] == unvisited {
+            comp[v] = num;
+            num += 1;
+        }
+    }
+
+    // 縮約後の辺は, 入る先の頂点の min_in のコストを引いたもの
+    let mut next = Vec::new();
+    let mut origin = Vec::new();
+    for (i, &(u, v, c)) in edges.iter().enumerate() {
+        if comp[u] == comp[v] || v == root {
+            continue;
+        }
+        next.push((comp[u], comp[v], c - edges[min_in[v].unwrap()].2));
+        origin.push(i);
+    }
+    let sub = arborescence_(num, &next, comp[root])?;
+
+    let mut res = Vec::with_capacity(n);
+    let mut entered = vec![false; n];
+    for j in sub {
+        let i = origin[j];
+        entered[edges[i].1] = true;
+        res.push(i);
+    }
+    // 閉路の辺は, 外から入ってきた頂点への辺を除いてすべて使う
+    for v in 0..n {
+        if in_cycle[v] && !entered[v] {
+            res.push(min_in[v].unwrap());
+        }
+    }
+    Some(res)
+}
@@ -0,0 +1,201 @@
+use std::rc::Rc;
+trait Monoid {
+    fn unity() -> Self;
+    fn op(&self, other: &Self) -> Self;
+}
+
+// ParsistentArray と同じ経路コピーに, 各ノードの総積を持たせたもの
+#[derive(Eq, PartialEq, Clone, Debug)]
+enum PersistentSegmentTree<T>
+where
+    T: Monoid + std::clone::Clone,
+{
+    Node(usize, T, Rc<PersistentSegmentTree<T>>, Rc<PersistentSegmentTree<T>>),
+    Leaf(T),
+}
+
+impl<T> PersistentSegmentTree<T>
+where
+    T: Monoid + std::clone::Clone,
+{
+    pub fn new(n: usize) -> Self {
+        let mut l = 1;
+        while l < n {
+            l *= 2;
+        }
+        Rc::try_unwrap(Self::new_(l)).ok().unwrap()
+    }
+
+    fn new_(n: usize) -> Rc<Self> {
+        if n == 1 {
+            Rc::new(PersistentSegmentTree::Leaf(T::unity()))
+        } else {
+            let child = Self::new_(n / 2);
+            let val = child.prod().op(&child.prod());
+            Rc::new(PersistentSegmentTree::Node(n, val, child.clone(), child))
+        }
+    }
+
+    pub fn from_vec(a: &Vec<T>) -> Self {
+        let mut l = 1;
+        while l < a.len() {
+            l *= 2;
+        }
+        Rc::try_unwrap(Self::from_slice_(a, 0, l)).ok().unwrap()
+    }
+
+    fn from_slice_(a: &Vec<T>, s: usize, n: usize) -> Rc<Self> {
+        if n == 1 {
+            let val = if s < a.len() { a[s].clone() } else { T::unity() };
+            Rc::new(PersistentSegmentTree::Leaf(val))
+        } else {
+            let left = Self::from_slice_(a, s, n / 2);
+            let right = Self::from_slice_(a, s + n / 2, n / 2);
+            let val = left.prod().op(&right.prod());
+            Rc::new(PersistentSegmentTree::Node(n, val, left, right))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            &PersistentSegmentTree::Leaf(_) => 1,
+            &PersistentSegmentTree::Node(n, _, _, _) => n,
+        }
+    }
+
+    // 全体の総積
+    pub fn prod(&self) -> &T {
+        match self {
+            &PersistentSegmentTree::Leaf(ref val) => val,
+            &PersistentSegmentTree::Node(_, ref val, _, _) => val,
+        }
+    }
+
+    pub fn update(&self, i: usize, val: T) -> Self {
+        debug_assert!(i < self.len());
+        match self {
+            &PersistentSegmentTree::Leaf(_) => PersistentSegmentTree::Leaf(val),
+            &PersistentSegmentTree::Node(n, _, ref left, ref right) => if i < n / 2 {
+                let left = Rc::new(left.update(i, val));
+                let val = left.prod().op(right.prod());
+                PersistentSegmentTree::Node(n, val, left, right.clone())
+            } else {
+                let right = Rc::new(right.update(i - n / 2, val));
+                let val = left.prod().op(right.prod());
+                PersistentSegmentTree::Node(n, val, left.clone(), right)
+            },
+        }
+    }
+
+    pub fn get(&self, i: usize) -> &T {
+        debug_assert!(i < self.len());
+        match self {
+            &PersistentSegmentTree::Leaf(ref val) => val,
+            &PersistentSegmentTree::Node(n, _, ref left, ref right) => if i < n / 2 {
+                left.get(i)
+            } else {
+                right.get(i - n / 2)
+            },
+        }
+    }
+
+    // [l, r) の総積
+    pub fn query(&self, l: usize, r: usize) -> T {
+        let n = self.len();
+        if r <= l || r == 0 || n <= l {
+            return T::unity();
+        }
+        if l == 0 && n <= r {
+            return self.prod().clone();
+        }
+        match self {
+            &PersistentSegmentTree::Leaf(ref val) => val.clone(),
+            &PersistentSegmentTree::Node(n, _, ref left, ref right) => {
+                let m = n / 2;
+                let retl = left.query(l, std::cmp::min(r, m));
+                let retr = if r > m {
+                    right.query(if l > m { l - m } else { 0 }, r - m)
+                } else {
+                    T::unity()
+                };
+                retl.op(&retr)
+            }
+        }
+    }
+}
+
+// RangeKth で使う出現回数. usize に直接 Monoid を実装すると他の実装とぶつかるので包む
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Count(usize);
+
+impl Monoid for Count {
+    fn unity() -> Count {
+        Count(0)
+    }
+
+    fn op(&self, other: &Count) -> Count {
+        Count(self.0 + other.0)
+    }
+}
+
+// a[0..i] の値ごとの出現回数を版 i として持ち, 区間 [l, r) の k 番目 (0-indexed) に小さい値を求める
+#[derive(Clone, Debug)]
+struct RangeKth<T> {
+    vals: Vec<T>,
+    roots: Vec<PersistentSegmentTree<Count>>,
+}
+
+impl<T> RangeKth<T>
+where
+    T: Ord + std::clone::Clone,
+{
+    fn new(a: &Vec<T>) -> Self {
+        let mut vals = a.clone();
+        vals.sort();
+        vals.dedup();
+        let mut roots: Vec<PersistentSegmentTree<Count>> = Vec::with_capacity(a.len() + 1);
+        roots.push(PersistentSegmentTree::new(vals.len()));
+        for e in a {
+            let i = vals.binary_search(e).unwrap();
+            let next = {
+                let last = roots.last().unwrap();
+                last.update(i, Count(last.get(i).0 + 1))
+            };
+            roots.push(next);
+        }
+        RangeKth {
+            vals: vals,
+            roots: roots,
+        }
+    }
+
+    fn kth(&self, l: usize, r: usize, k: usize) -> T {
+        assert!(l <= r && k < r - l);
+        let i = Self::kth_(&self.roots[l], &self.roots[r], k);
+        self.vals[i].clone()
+    }
+
+    fn kth_(a: &PersistentSegmentTree<Count>, b: &PersistentSegmentTree<Count>, k: usize) -> usize {
+        use PersistentSegmentTree::*;
+        match (a, b) {
+            (&Node(n, _, ref al, ref ar), &Node(_, _, ref bl, ref br)) => {
+                let c = bl.prod().0 - al.prod().0;
+                if k < c {
+                    Self::kth_(al, bl, k)
+                } else {
+                    n / 2 + Self::kth_(ar, br, k - c)
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    // [l, r) のうち x 未満の値の個数
+    fn count_less(&self, l: usize, r: usize, x: &T) -> usize {
+        let i = match self.vals.binary_search(x) {
+            Ok(i) => i,
+            Err(i) => i,
+        };
+        self.roots[r].query(0, i).0 - self.roots[l].query(0, i).0
+    }
+}
@@ -0,0 +1,147 @@
+// Graph_new.rs と一緒に使う
+
+// comp[v]: v の属する強連結成分の番号. 番号はトポロジカル順 (辺 u -> v なら comp[u] <= comp[v])
+#[derive(Clone, Debug)]
+struct SCC {
+    comp: Vec<usize>,
+    num: usize,
+}
+
+impl SCC {
+    fn groups(&self) -> Vec<Vec<usize>> {
+        let mut res = vec![Vec::new(); self.num];
+        for (v, &c) in self.comp.iter().enumerate() {
+            res[c].push(v);
+        }
+        res
+    }
+
+    // 成分を頂点とする DAG. 同じ成分の組の間の辺は最初のものだけを残す
+    fn condense<G: Graph>(&self, g: &G) -> AdjList<G::Cost> {
+        let mut res = AdjList::new(self.num);
+        let mut used = std::collections::HashSet::new();
+        for from in 0..g.len() {
+            for e in g.get(from) {
+                let (a, b) = (self.comp[from], self.comp[e.to]);
+                if a != b && used.insert((a, b)) {
+                    res.add_edge(a, b, e.cost.clone());
+                }
+            }
+        }
+        res
+    }
+}
+
+trait StronglyConnected: Graph {
+    fn scc(&self) -> SCC;
+}
+
+impl<T: Graph> StronglyConnected for T {
+    // Tarjan (再帰を使わない)
+    fn scc(&self) -> SCC {
+        let n = self.len();
+        let unvisited = std::usize::MAX;
+        let mut ord = vec![unvisited; n];
+        let mut low = vec![0; n];
+        let mut comp = vec![unvisited; n];
+        let mut stack = Vec::new();
+        let mut time = 0;
+        let mut num = 0;
+        for s in 0..n {
+            if ord[s] != unvisited {
+                continue;
+            }
+            let mut call = vec![(s, self.get(s))];
+            ord[s] = time;
+            low[s] = time;
+            time += 1;
+            stack.push(s);
+            while let Some((v, mut iter)) = call.pop() {
+                if let Some(e) = iter.next() {
+                    call.push((v, iter));
+                    let to = e.to;
+                    if ord[to] == unvisited {
+                        ord[to] = time;
+                        low[to] = time;
+                        time += 1;
+                        stack.push(to);
+                        call.push((to, self.get(to)));
+                    } else if comp[to] == unvisited {
+                        low[v] = std::cmp::min(low[v], ord[to]);
+                    }
+                    continue;
+                }
+                if low[v] == ord[v] {
+                    while let Some(u) = stack.pop() {
+                        comp[u] = num;
+                        if u == v {
+                            break;
+                        }
+                    }
+                    num += 1;
+                }
+                if let Some(&(p, _)) = call.last() {
+                    low[p] = std::cmp::min(low[p], low[v]);
+                }
+            }
+        }
+        // Tarjan は逆トポロジカル順に番号を振るので反転する
+        for c in comp.iter_mut() {
+            *c = num - 1 - *c;
+        }
+        SCC {
+            comp: comp,
+            num: num,
+        }
+    }
+}
+
+// 変数 i が f であることを頂点 2*i + (f as usize) で表す
+#[derive(Clone, Debug)]
+struct TwoSat {
+    n: usize,
+    g: AdjList<()>,
+}
+
+impl TwoSat {
+    fn new(n: usize) -> TwoSat {
+        TwoSat {
+            n: n,
+            g: AdjList::new(2 * n),
+        }
+    }
+
+    fn node(i: usize, f: bool) -> usize {
+        2 * i + f as usize
+    }
+
+    // (x_i == f) || (x_j == g)
+    fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        assert!(i < self.n && j < self.n);
+        self.g.add_edge(Self::node(i, !f), Self::node(j, g), ());
+        self.g.add_edge(Self::node(j, !g), Self::node(i, f), ());
+    }
+
+    // (x_i == f) ならば (x_j == g)
+    fn add_implication(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.add_clause(i, !f, j, g);
+    }
+
+    // x_i == f を強制する
+    fn set(&mut self, i: usize, f: bool) {
+        self.add_clause(i, f, i, f);
+    }
+
+    fn solve(&self) -> Option<Vec<bool>> {
+        let scc = self.g.scc();
+        let mut res = Vec::with_capacity(self.n);
+        for i in 0..self.n {
+            let (t, f) = (scc.comp[Self::node(i, true)], scc.comp[Self::node(i, false)]);
+            if t == f {
+                return None;
+            }
+            res.push(t > f);
+        }
+        Some(res)
+    }
+}
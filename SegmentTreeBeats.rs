@@ -0,0 +1,325 @@
+// Segment Tree Beats
+// range chmin / range chmax / range add / range sum
+#[derive(Clone, Debug)]
+struct SegmentTreeBeats {
+    max1: Vec<i64>,
+    max2: Vec<i64>,
+    maxc: Vec<usize>,
+    min1: Vec<i64>,
+    min2: Vec<i64>,
+    minc: Vec<usize>,
+    sum: Vec<i64>,
+    add: Vec<i64>,
+    width: Vec<usize>,
+    size: usize,
+    length: usize,
+}
+
+impl SegmentTreeBeats {
+    const INF: i64 = std::i64::MAX;
+    const NINF: i64 = std::i64::MIN;
+
+    fn new(n: usize) -> SegmentTreeBeats {
+        Self::from_vec(&vec![0; n])
+    }
+
+    fn from_vec(a: &Vec<i64>) -> SegmentTreeBeats {
+        let n = a.len();
+        let mut size = 1;
+        while size < n {
+            size <<= 1;
+        }
+        let mut res = SegmentTreeBeats {
+            max1: vec![Self::NINF; 2 * size],
+            max2: vec![Self::NINF; 2 * size],
+            maxc: vec![0; 2 * size],
+            min1: vec![Self::INF; 2 * size],
+            min2: vec![Self::INF; 2 * size],
+            minc: vec![0; 2 * size],
+            sum: vec![0; 2 * size],
+            add: vec![0; 2 * size],
+            width: vec![0; 2 * size],
+            size: size,
+            length: n,
+        };
+        for i in 0..n {
+            let v = size + i;
+            res.max1[v] = a[i];
+            res.min1[v] = a[i];
+            res.maxc[v] = 1;
+            res.minc[v] = 1;
+            res.sum[v] = a[i];
+            res.width[v] = 1;
+        }
+        for v in (1..size).rev() {
+            res.width[v] = res.width[2 * v] + res.width[2 * v + 1];
+            res.pull(v);
+        }
+        res
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn pull(&mut self, v: usize) {
+        let (l, r) = (2 * v, 2 * v + 1);
+        self.sum[v] = self.sum[l] + self.sum[r];
+
+        if self.max1[l] == self.max1[r] {
+            self.max1[v] = self.max1[l];
+            self.max2[v] = std::cmp::max(self.max2[l], self.max2[r]);
+            self.maxc[v] = self.maxc[l] + self.maxc[r];
+        } else if self.max1[l] > self.max1[r] {
+            self.max1[v] = self.max1[l];
+            self.max2[v] = std::cmp::max(self.max2[l], self.max1[r]);
+            self.maxc[v] = self.maxc[l];
+        } else {
+            self.max1[v] = self.max1[r];
+            self.max2[v] = std::cmp::max(self.max1[l], self.max2[r]);
+            self.maxc[v] = self.maxc[r];
+        }
+
+        if self.min1[l] == self.min1[r] {
+            self.min1[v] = self.min1[l];
+            self.min2[v] = std::cmp::min(self.min2[l], self.min2[r]);
+            self.minc[v] = self.minc[l] + self.minc[r];
+        } else if self.min1[l] < self.min1[r] {
+            self.min1[v] = self.min1[l];
+            self.min2[v] = std::cmp::min(self.min2[l], self.min1[r]);
+            self.minc[v] = self.minc[l];
+        } else {
+            self.min1[v] = self.min1[r];
+            self.min2[v] = std::cmp::min(self.min1[l], self.min2[r]);
+            self.minc[v] = self.minc[r];
+        }
+    }
+
+    // 最大値を x に下げる (max2 < x < max1 のときのみ呼ぶ)
+    fn apply_chmin(&mut self, v: usize, x: i64) {
+        self.sum[v] += (x - self.max1[v]) * self.maxc[v] as i64;
+        if self.max1[v] == self.min1[v] {
+            self.min1[v] = x;
+        } else if self.max1[v] == self.min2[v] {
+            self.min2[v] = x;
+        }
+        self.max1[v] = x;
+    }
+
+    // 最小値を x に上げる (min1 < x < min2 のときのみ呼ぶ)
+    fn apply_chmax(&mut self, v: usize, x: i64) {
+        self.sum[v] += (x - self.min1[v]) * self.minc[v] as i64;
+        if self.min1[v] == self.max1[v] {
+            self.max1[v] = x;
+        } else if self.min1[v] == self.max2[v] {
+            self.max2[v] = x;
+        }
+        self.min1[v] = x;
+    }
+
+    fn apply_add(&mut self, v: usize, x: i64) {
+        if self.width[v] == 0 {
+            return;
+        }
+        self.max1[v] += x;
+        if self.max2[v] != Self::NINF {
+            self.max2[v] += x;
+        }
+        self.min1[v] += x;
+        if self.min2[v] != Self::INF {
+            self.min2[v] += x;
+        }
+        self.sum[v] += x * self.width[v] as i64;
+        self.add[v] += x;
+    }
+
+    fn propagate(&mut self, v: usize) {
+        if v >= self.size {
+            return;
+        }
+        let (l, r) = (2 * v, 2 * v + 1);
+        if self.add[v] != 0 {
+            let x = self.add[v];
+            self.apply_add(l, x);
+            self.apply_add(r, x);
+            self.add[v] = 0;
+        }
+        for &c in &[l, r] {
+            if self.max1[v] < self.max1[c] {
+                let x = self.max1[v];
+                self.apply_chmin(c, x);
+            }
+            if self.min1[v] > self.min1[c] {
+                let x = self.min1[v];
+                self.apply_chmax(c, x);
+            }
+        }
+    }
+
+    fn range_chmin(&mut self, l: usize, r: usize, x: i64) {
+        let size = self.size;
+        self.range_chmin_(1, 0, size, l, r, x);
+    }
+
+    fn range_chmin_(&mut self, v: usize, a: usize, b: usize, l: usize, r: usize, x: i64) {
+        if r <= a || b <= l || self.max1[v] <= x {
+            return;
+        }
+        if l <= a && b <= r && self.max2[v] < x {
+            self.apply_chmin(v, x);
+            return;
+        }
+        self.propagate(v);
+        let m = (a + b) / 2;
+        self.range_chmin_(2 * v, a, m, l, r, x);
+        self.range_chmin_(2 * v + 1, m, b, l, r, x);
+        self.pull(v);
+    }
+
+    fn range_chmax(&mut self, l: usize, r: usize, x: i64) {
+        let size = self.size;
+        self.range_chmax_(1, 0, size, l, r, x);
+    }
+
+    fn range_chmax_(&mut self, v: usize, a: usize, b: usize, l: usize, r: usize, x: i64) {
+        if r <= a || b <= l || self.min1[v] >= x {
+            return;
+        }
+        if l <= a && b <= r && self.min2[v] > x {
+            self.apply_chmax(v, x);
+            return;
+        }
+        self.propagate(v);
+        let m = (a + b) / 2;
+        self.range_chmax_(2 * v, a, m, l, r, x);
+        self.range_chmax_(2 * v + 1, m, b, l, r, x);
+        self.pull(v);
+    }
+
+    fn range_add(&mut self, l: usize, r: usize, x: i64) {
+        let size = self.size;
+        self.range_add_(1, 0, size, l, r, x);
+    }
+
+    fn range_add_(&mut self, v: usize, a: usize, b: usize, l: usize, r: usize, x: i64) {
+        if r <= a || b <= l {
+            return;
+        }
+        if l <= a && b <= r {
+            self.apply_add(v, x);
+            return;
+        }
+        self.propagate(v);
+        let m = (a + b) / 2;
+        self.range_add_(2 * v, a, m, l, r, x);
+        self.range_add_(2 * v + 1, m, b, l, r, x);
+        self.pull(v);
+    }
+
+    fn query_sum(&mut self, l: usize, r: usize) -> i64 {
+        let size = self.size;
+        self.query_sum_(1, 0, size, l, r)
+    }
+
+    fn query_sum_(&mut self, v: usize, a: usize, b: usize, l: usize, r: usize) -> i64 {
+        if r <= a || b <= l {
+            return 0;
+        }
+        if l <= a && b <= r {
+            return self.sum[v];
+        }
+        self.propagate(v);
+        let m = (a + b) / 2;
+        self.query_sum_(2 * v, a, m, l, r) + self.query_sum_(2 * v + 1, m, b, l, r)
+    }
+
+    fn query_max(&mut self, l: usize, r: usize) -> i64 {
+        let size = self.size;
+        self.query_max_(1, 0, size, l, r)
+    }
+
+    fn query_max_(&mut self, v: usize, a: usize, b: usize, l: usize, r: usize) -> i64 {
+        if r <= a || b <= l {
+            return Self::NINF;
+        }
+        if l <= a && b <= r {
+            return self.max1[v];
+        }
+        self.propagate(v);
+        let m = (a + b) / 2;
+        std::cmp::max(
+            self.query_max_(2 * v, a, m, l, r),
+            self.query_max_(2 * v + 1, m, b, l, r),
+        )
+    }
+
+    fn query_min(&mut self, l: usize, r: usize) -> i64 {
+        let size = self.size;
+        self.query_min_(1, 0, size, l, r)
+    }
+
+    fn query_min_(&mut self, v: usize, a: usize, b: usize, l: usize, r: usize) -> i64 {
+        if r <= a || b <= l {
+            return Self::INF;
+        }
+        if l <= a && b <= r {
+            return self.min1[v];
+        }
+        self.propagate(v);
+        let m = (a + b) / 2;
+        std::cmp::min(
+            self.query_min_(2 * v, a, m, l, r),
+            self.query_min_(2 * v + 1, m, b, l, r),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xorshift(x: &mut u64) -> u64 {
+        *x ^= *x << 13;
+        *x ^= *x >> 7;
+        *x ^= *x << 17;
+        *x
+    }
+
+    #[test]
+    fn random_against_naive() {
+        let mut seed = 88172645463325252;
+        for _ in 0..200 {
+            let n = (xorshift(&mut seed) % 20 + 1) as usize;
+            let mut naive: Vec<i64> = (0..n).map(|_| (xorshift(&mut seed) % 201) as i64 - 100).collect();
+            let mut seg = SegmentTreeBeats::from_vec(&naive);
+            for _ in 0..200 {
+                let l = (xorshift(&mut seed) % n as u64) as usize;
+                let r = l + 1 + (xorshift(&mut seed) % (n - l) as u64) as usize;
+                let x = (xorshift(&mut seed) % 201) as i64 - 100;
+                match xorshift(&mut seed) % 6 {
+                    0 => {
+                        seg.range_chmin(l, r, x);
+                        for a in &mut naive[l..r] {
+                            *a = std::cmp::min(*a, x);
+                        }
+                    }
+                    1 => {
+                        seg.range_chmax(l, r, x);
+                        for a in &mut naive[l..r] {
+                            *a = std::cmp::max(*a, x);
+                        }
+                    }
+                    2 => {
+                        seg.range_add(l, r, x);
+                        for a in &mut naive[l..r] {
+                            *a += x;
+                        }
+                    }
+                    3 => assert_eq!(seg.query_sum(l, r), naive[l..r].iter().sum::<i64>()),
+                    4 => assert_eq!(seg.query_max(l, r), *naive[l..r].iter().max().unwrap()),
+                    _ => assert_eq!(seg.query_min(l, r), *naive[l..r].iter().min().unwrap()),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+trait Monoid {
+    fn unity() -> Self;
+    fn op(&self, other: &Self) -> Self;
+}
+
+// op が冪等 (min, max, gcd など) のときのみ正しい
+#[derive(Clone, Debug)]
+struct SparseTable<T> {
+    table: Vec<Vec<T>>,
+}
+
+impl<T: Monoid + std::clone::Clone> SparseTable<T> {
+    fn new(a: &Vec<T>) -> SparseTable<T> {
+        let n = a.len();
+        let mut table = vec![a.clone()];
+        let mut k = 1;
+        while 2 * k <= n {
+            let next = {
+                let prev = table.last().unwrap();
+                (0..n + 1 - 2 * k).map(|i| prev[i].op(&prev[i + k])).collect()
+            };
+            table.push(next);
+            k *= 2;
+        }
+        SparseTable { table: table }
+    }
+
+    fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    // [l, r)
+    fn query(&self, l: usize, r: usize) -> T {
+        assert!(l <= r && r <= self.len());
+        if l == r {
+            return T::unity();
+        }
+        let k = (0usize.leading_zeros() - (r - l).leading_zeros() - 1) as usize;
+        self.table[k][l].op(&self.table[k][r - (1 << k)])
+    }
+}
+
+// 任意の結合的な op に使える
+#[derive(Clone, Debug)]
+struct DisjointSparseTable<T> {
+    table: Vec<Vec<T>>,
+    length: usize,
+}
+
+impl<T: Monoid + std::clone::Clone> DisjointSparseTable<T> {
+    fn new(a: &Vec<T>) -> DisjointSparseTable<T> {
+        let n = a.len();
+        let mut size = 1;
+        let mut log = 1;
+        while size < n {
+            size <<= 1;
+            log += 1;
+        }
+        let mut table = vec![a.clone(); log];
+        // 段 k では幅 2^k のブロックの中点から左右へ累積をとる
+        for k in 1..log {
+            let half = 1 << (k - 1);
+            let mut m = half;
+            while m - half < n {
+                let row = &mut table[k];
+                if m - 1 < n {
+                    for i in (m - half..m - 1).rev() {
+                        row[i] = a[i].op(&row[i + 1]);
+                    }
+                }
+                for i in m + 1..std::cmp::min(m + half, n) {
+                    row[i] = row[i - 1].op(&a[i]);
+                }
+                m += 2 * half;
+            }
+        }
+        DisjointSparseTable {
+            table: table,
+            length: n,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    // [l, r)
+    fn query(&self, l: usize, r: usize) -> T {
+        assert!(l <= r && r <= self.len());
+        if l == r {
+            return T::unity();
+        }
+        let r = r - 1;
+        if l == r {
+            return self.table[0][l].clone();
+        }
+        let k = (0usize.leading_zeros() - (l ^ r).leading_zeros()) as usize;
+        self.table[k][l].op(&self.table[k][r])
+    }
+}
@@ -0,0 +1,152 @@
+// Graph_new.rs と一緒に使う
+// 無向の木 (add_uedge で作ったもの) を root で根付ける
+
+#[derive(Clone, Debug)]
+struct RootedTree<Cost> {
+    root: usize,
+    // 根の親は usize::MAX
+    par: Vec<usize>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    // 根からの辺のコストの和
+    dist: Vec<Cost>,
+    // DFS の行きがけ順
+    order: Vec<usize>,
+    // up[k][v]: v の 2^k 個上の祖先 (根を越えるときは根)
+    up: Vec<Vec<usize>>,
+}
+
+impl<Cost> RootedTree<Cost>
+where
+    Cost: Add<Cost, Output = Cost> + Sub<Cost, Output = Cost> + Zero + Copy,
+{
+    fn new<G: Graph<Cost = Cost>>(g: &G, root: usize) -> RootedTree<Cost> {
+        let n = g.len();
+        let unvisited = std::usize::MAX;
+        let mut par = vec![unvisited; n];
+        let mut depth = vec![0; n];
+        let mut dist = vec![Cost::zero(); n];
+        let mut order = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        visited[root] = true;
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for e in g.get(v) {
+                if visited[e.to] {
+                    continue;
+                }
+                visited[e.to] = true;
+                par[e.to] = v;
+                depth[e.to] = depth[v] + 1;
+                dist[e.to] = dist[v] + e.cost;
+                stack.push(e.to);
+            }
+        }
+        let mut size = vec![1; n];
+        for &v in order.iter().rev() {
+            if par[v] != unvisited {
+                size[par[v]] += size[v];
+            }
+        }
+
+        let mut log = 1;
+        while 1 << log < n {
+            log += 1;
+        }
+        let mut up = vec![(0..n).map(|v| if par[v] == unvisited { v } else { par[v] }).collect::<Vec<_>>()];
+        for k in 1..log {
+            let next = (0..n).map(|v| up[k - 1][up[k - 1][v]]).collect();
+            up.push(next);
+        }
+        RootedTree {
+            root: root,
+            par: par,
+            depth: depth,
+            size: size,
+            dist: dist,
+            order: order,
+            up: up,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.par.len()
+    }
+
+    // v の k 個上の祖先. 根より上なら None
+    fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+        let mut v = v;
+        for i in 0..self.up.len() {
+            if k >> i & 1 == 1 {
+                v = self.up[i][v];
+            }
+        }
+        Some(v)
+    }
+
+    fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = if self.depth[u] < self.depth[v] { (v, u) } else { (u, v) };
+        u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+        if u == v {
+            return u;
+        }
+        for k in (0..self.up.len()).rev() {
+            if self.up[k][u] != self.up[k][v] {
+                u = self.up[k][u];
+                v = self.up[k][v];
+            }
+        }
+        self.up[0][u]
+    }
+
+    // 辺の本数での距離
+    fn depth_distance(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    // 辺のコストでの距離
+    fn distance(&self, u: usize, v: usize) -> Cost {
+        let w = self.lca(u, v);
+        self.dist[u] + self.dist[v] - self.dist[w] - self.dist[w]
+    }
+
+    // u から v へのパス上で u から k 番目の頂点. パスの長さを越えれば None
+    fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let (du, dv) = (self.depth[u] - self.depth[w], self.depth[v] - self.depth[w]);
+        if k <= du {
+            self.kth_ancestor(u, k)
+        } else if k <= du + dv {
+            self.kth_ancestor(v, du + dv - k)
+        } else {
+            None
+        }
+    }
+
+    // v の部分木に u が含まれるか
+    fn is_ancestor(&self, v: usize, u: usize) -> bool {
+        self.depth[u] >= self.depth[v] && self.kth_ancestor(u, self.depth[u] - self.depth[v]) == Some(v)
+    }
+}
+
+// 木の直径 (コストの和, パスの頂点列). 辺のコストは非負とする
+fn tree_diameter<G>(g: &G) -> (G::Cost, Vec<usize>)
+where
+    G: Graph,
+    G::Cost: Ord + Add<G::Cost, Output = G::Cost> + Sub<G::Cost, Output = G::Cost> + Zero + Copy,
+{
+    let farthest = |t: &RootedTree<G::Cost>| (0..t.len()).max_by_key(|&v| t.dist[v]).unwrap();
+    let s = farthest(&RootedTree::new(g, 0));
+    let t = RootedTree::new(g, s);
+    let mut v = farthest(&t);
+    let mut path = vec![v];
+    while v != s {
+        v = t.par[v];
+        path.push(v);
+    }
+    (t.dist[path[0]], path)
+}
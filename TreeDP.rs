@@ -0,0 +1,195 @@
+// Graph_new.rs と一緒に使う
+// 無向の森 (add_uedge で作ったもの) を仮定する
+trait Monoid {
+    fn unity() -> Self;
+    fn op(&self, other: &Self) -> Self;
+}
+
+#[derive(Clone, Debug)]
+struct CentroidDecomposition {
+    // 重心分解の木での親 (根なら usize::MAX)
+    par: Vec<usize>,
+    // 重心分解の木での深さ. 同じ level の subtree は互いに素
+    level: Vec<usize>,
+    // subtree[c]: c を重心に選んだときの連結成分の頂点 (先頭は c)
+    subtree: Vec<Vec<usize>>,
+    // 重心を選んだ順 (親は子より先)
+    order: Vec<usize>,
+}
+
+impl CentroidDecomposition {
+    fn new<G: Graph>(g: &G) -> CentroidDecomposition {
+        let n = g.len();
+        let unvisited = std::usize::MAX;
+        let mut removed = vec![false; n];
+        let mut par = vec![unvisited; n];
+        let mut level = vec![0; n];
+        let mut subtree = vec![vec![]; n];
+        let mut order = Vec::with_capacity(n);
+        let mut size = vec![0; n];
+        // 連結成分の親 (成分ごとに使い回す)
+        let mut tmp_par = vec![unvisited; n];
+        // (成分の頂点, 重心分解の木での親)
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for s in 0..n {
+            if removed[s] {
+                continue;
+            }
+            stack.push((s, unvisited));
+            while let Some((s, p)) = stack.pop() {
+                let mut comp = vec![s];
+                tmp_par[s] = unvisited;
+                let mut i = 0;
+                while i < comp.len() {
+                    let v = comp[i];
+                    for e in g.get(v) {
+                        if !removed[e.to] && e.to != tmp_par[v] {
+                            tmp_par[e.to] = v;
+                            comp.push(e.to);
+                        }
+                    }
+                    i += 1;
+                }
+                for &v in comp.iter().rev() {
+                    size[v] = 1;
+                    for e in g.get(v) {
+                        if !removed[e.to] && e.to != tmp_par[v] {
+                            size[v] += size[e.to];
+                        }
+                    }
+                }
+                // 根から重い方へ降りていき, 残りがすべて半分以下になる頂点で止まる
+                let total = comp.len();
+                let mut c = s;
+                loop {
+                    let next = g
+                        .get(c)
+                        .map(|e| e.to)
+                        .find(|&to| !removed[to] && to != tmp_par[c] && size[to] * 2 > total);
+                    match next {
+                        Some(to) => c = to,
+                        None => break,
+                    }
+                }
+                let pos = comp.iter().position(|&v| v == c).unwrap();
+                comp.swap(0, pos);
+
+                removed[c] = true;
+                par[c] = p;
+                level[c] = if p == unvisited { 0 } else { level[p] + 1 };
+                order.push(c);
+                for e in g.get(c) {
+                    if !removed[e.to] {
+                        stack.push((e.to, c));
+                    }
+                }
+                subtree[c] = comp;
+            }
+        }
+        CentroidDecomposition {
+            par: par,
+            level: level,
+            subtree: subtree,
+            order: order,
+        }
+    }
+
+    // 重心分解の木 (親子の間に無向辺)
+    fn centroid_tree(&self) -> AdjList<()> {
+        let n = self.par.len();
+        let mut res = AdjList::new(n);
+        for v in 0..n {
+            if self.par[v] != std::usize::MAX {
+                res.add_uedge(self.par[v], v, ());
+            }
+        }
+        res
+    }
+
+    // v を含む subtree の重心を, v 自身から根に向かって並べたもの. 長さは O(log n)
+    fn ancestors(&self, v: usize) -> Vec<usize> {
+        let mut res = vec![v];
+        let mut v = v;
+        while self.par[v] != std::usize::MAX {
+            v = self.par[v];
+            res.push(v);
+        }
+        res
+    }
+}
+
+// 全方位木 DP
+// dp[v] = finalize(lift(dp[c], v, e) を v の隣接辺 e (c = e.to) について掛けたもの, v)
+// ただし dp[c] は v を取り除いた側の, c を根とする部分木での値
+// 各頂点を根としたときの dp の値を返す
+fn rerooting<G, M, V, L, F>(g: &G, lift: L, finalize: F) -> Vec<V>
+where
+    G: Graph,
+    M: Monoid + Clone,
+    V: Clone,
+    L: Fn(&V, usize, &Edge<G::Cost>) -> M,
+    F: Fn(&M, usize) -> V,
+{
+    let n = g.len();
+    let unvisited = std::usize::MAX;
+    let mut par = vec![unvisited; n];
+    let mut order = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    for s in 0..n {
+        if visited[s] {
+            continue;
+        }
+        visited[s] = true;
+        let mut stack = vec![s];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            for e in g.get(v) {
+                if !visited[e.to] {
+                    visited[e.to] = true;
+                    par[e.to] = v;
+                    stack.push(e.to);
+                }
+            }
+        }
+    }
+
+    // 葉から: down[v] は v を根とする部分木での値
+    let mut down: Vec<Option<V>> = vec![None; n];
+    for &v in order.iter().rev() {
+        let mut acc = M::unity();
+        for e in g.get(v) {
+            if e.to != par[v] {
+                acc = acc.op(&lift(down[e.to].as_ref().unwrap(), v, e));
+            }
+        }
+        down[v] = Some(finalize(&acc, v));
+    }
+
+    // 根から: up[v] は v を取り除いた側の, par[v] を根とする部分木での値
+    let mut up: Vec<Option<V>> = vec![None; n];
+    let mut res: Vec<Option<V>> = vec![None; n];
+    for &v in &order {
+        let values: Vec<M> = g
+            .get(v)
+            .map(|e| {
+                let x = if e.to == par[v] { &up[v] } else { &down[e.to] };
+                lift(x.as_ref().unwrap(), v, e)
+            })
+            .collect();
+        let k = values.len();
+        // suffix[i] = values[i..] の積
+        let mut suffix = vec![M::unity(); k + 1];
+        for i in (0..k).rev() {
+            suffix[i] = values[i].op(&suffix[i + 1]);
+        }
+        let mut prefix = M::unity();
+        for (i, e) in g.get(v).enumerate() {
+            if e.to != par[v] {
+                up[e.to] = Some(finalize(&prefix.op(&suffix[i + 1]), v));
+            }
+            prefix = prefix.op(&values[i]);
+        }
+        res[v] = Some(finalize(&suffix[0], v));
+    }
+    res.into_iter().map(|x| x.unwrap()).collect()
+}
@@ -0,0 +1,197 @@
+#[derive(Clone, Debug)]
+struct BitVector {
+    bits: Vec<u64>,
+    // sum[i]: bits[0..i] に含まれる 1 の個数
+    sum: Vec<u32>,
+}
+
+impl BitVector {
+    fn new(v: &Vec<bool>) -> BitVector {
+        let m = v.len() / 64 + 1;
+        let mut bits = vec![0u64; m];
+        for (i, &b) in v.iter().enumerate() {
+            if b {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let mut sum = vec![0; m + 1];
+        for i in 0..m {
+            sum[i + 1] = sum[i] + bits[i].count_ones();
+        }
+        BitVector {
+            bits: bits,
+            sum: sum,
+        }
+    }
+
+    fn access(&self, i: usize) -> bool {
+        self.bits[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    // [0, i) の 1 の個数
+    fn rank1(&self, i: usize) -> usize {
+        let mask = (1u64 << (i % 64)).wrapping_sub(1);
+        (self.sum[i / 64] + (self.bits[i / 64] & mask).count_ones()) as usize
+    }
+
+    fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+}
+
+// 値は [0, 2^log) に収まっていなければならない
+#[derive(Clone, Debug)]
+struct WaveletMatrix {
+    mat: Vec<BitVector>,
+    zeros: Vec<usize>,
+    log: usize,
+    length: usize,
+}
+
+impl WaveletMatrix {
+    fn new(a: &Vec<u64>) -> WaveletMatrix {
+        let max = a.iter().cloned().max().unwrap_or(0);
+        let mut log = 1;
+        while log < 64 && max >> log > 0 {
+            log += 1;
+        }
+        Self::with_log(a, log)
+    }
+
+    fn with_log(a: &Vec<u64>, log: usize) -> WaveletMatrix {
+        let n = a.len();
+        let mut cur = a.clone();
+        let mut mat = vec![];
+        let mut zeros = vec![];
+        for lv in (0..log).rev() {
+            let bits: Vec<bool> = cur.iter().map(|&x| x >> lv & 1 == 1).collect();
+            let mut next = Vec::with_capacity(n);
+            next.extend(cur.iter().filter(|&&x| x >> lv & 1 == 0));
+            zeros.push(next.len());
+            next.extend(cur.iter().filter(|&&x| x >> lv & 1 == 1));
+            mat.push(BitVector::new(&bits));
+            cur = next;
+        }
+        WaveletMatrix {
+            mat: mat,
+            zeros: zeros,
+            log: log,
+            length: n,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    // mat[d] は上から d 番目のビット (値のビット log-1-d) に対応する
+    fn down(&self, d: usize, bit: bool, l: usize, r: usize) -> (usize, usize) {
+        if bit {
+            let z = self.zeros[d];
+            (z + self.mat[d].rank1(l), z + self.mat[d].rank1(r))
+        } else {
+            (self.mat[d].rank0(l), self.mat[d].rank0(r))
+        }
+    }
+
+    fn access(&self, i: usize) -> u64 {
+        assert!(i < self.len());
+        let mut res = 0;
+        let mut i = i;
+        for d in 0..self.log {
+            let bit = self.mat[d].access(i);
+            res = res << 1 | bit as u64;
+            i = if bit {
+                self.zeros[d] + self.mat[d].rank1(i)
+            } else {
+                self.mat[d].rank0(i)
+            };
+        }
+        res
+    }
+
+    // [0, r) に含まれる x の個数
+    fn rank(&self, x: u64, r: usize) -> usize {
+        if self.log < 64 && x >> self.log > 0 {
+            return 0;
+        }
+        let (mut l, mut r) = (0, r);
+        for d in 0..self.log {
+            let bit = x >> (self.log - 1 - d) & 1 == 1;
+            let next = self.down(d, bit, l, r);
+            l = next.0;
+            r = next.1;
+        }
+        r - l
+    }
+
+    // [l, r) の中で k 番目 (0-indexed) に小さい値
+    fn kth_smallest(&self, l: usize, r: usize, k: usize) -> u64 {
+        assert!(l <= r && r <= self.len() && k < r - l);
+        let (mut l, mut r, mut k) = (l, r, k);
+        let mut res = 0;
+        for d in 0..self.log {
+            let cnt0 = self.mat[d].rank0(r) - self.mat[d].rank0(l);
+            let bit = k >= cnt0;
+            if bit {
+                k -= cnt0;
+            }
+            res = res << 1 | bit as u64;
+            let next = self.down(d, bit, l, r);
+            l = next.0;
+            r = next.1;
+        }
+        res
+    }
+
+    fn kth_largest(&self, l: usize, r: usize, k: usize) -> u64 {
+        self.kth_smallest(l, r, r - l - k - 1)
+    }
+
+    // [l, r) の中で upper 未満の値の個数
+    fn count_less(&self, l: usize, r: usize, upper: u64) -> usize {
+        if self.log < 64 && upper >> self.log > 0 {
+            return r - l;
+        }
+        let (mut l, mut r) = (l, r);
+        let mut res = 0;
+        for d in 0..self.log {
+            let bit = upper >> (self.log - 1 - d) & 1 == 1;
+            if bit {
+                res += self.mat[d].rank0(r) - self.mat[d].rank0(l);
+            }
+            let next = self.down(d, bit, l, r);
+            l = next.0;
+            r = next.1;
+        }
+        res
+    }
+
+    // [l, r) の中で a <= x < b を満たす値の個数
+    fn range_freq(&self, l: usize, r: usize, a: u64, b: u64) -> usize {
+        if a >= b {
+            return 0;
+        }
+        self.count_less(l, r, b) - self.count_less(l, r, a)
+    }
+
+    // [l, r) の中で upper 未満の最大の値
+    fn prev_value(&self, l: usize, r: usize, upper: u64) -> Option<u64> {
+        let cnt = self.count_less(l, r, upper);
+        if cnt == 0 {
+            None
+        } else {
+            Some(self.kth_smallest(l, r, cnt - 1))
+        }
+    }
+
+    // [l, r) の中で lower 以上の最小の値
+    fn next_value(&self, l: usize, r: usize, lower: u64) -> Option<u64> {
+        let cnt = self.count_less(l, r, lower);
+        if cnt == r - l {
+            None
+        } else {
+            Some(self.kth_smallest(l, r, cnt))
+        }
+    }
+}
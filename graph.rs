@@ -104,6 +104,10 @@ where
         heap.push(Edge{to: from, cost: Cost::zero()});
         dist[from] = Cost::zero();
         while let Some(p) = heap.pop() {
+            // 古いエントリは読み飛ばす. 経路復元つきの dijkstra は Graph_new.rs にある
+            if p.cost > dist[p.to] {
+                continue;
+            }
             for e in &(*self)[p.to] {
                 if p.cost+e.cost >= dist[e.to] {
                     continue;
@@ -2,34 +2,346 @@ fn sliding_minimum<T>(a: Vec<T>, width: usize) -> Vec<T>
 where
     T: std::cmp::PartialOrd + Clone + std::fmt::Debug,
 {
-    let mut res = Vec::with_capacity(a.len()-width);
-    let mut set = std::collections::VecDeque::new();
-    for (i, e) in a.iter().enumerate().take(width) {
-        while let Some((v, i)) = set.pop_back() {
-            if v < e {
-                set.push_back((v, i));
+    sliding_min(&a, width).cloned().collect()
+}
+
+// 幅 width の窓を左から順に動かしたときの各窓での最小 (最大) 値への参照を返す
+// width > a.len() のときは何も返さない
+struct SlidingWindowExtremum<'a, T: 'a> {
+    a: &'a [T],
+    width: usize,
+    pos: usize,
+    deque: std::collections::VecDeque<usize>,
+    // better(x, y): x が y より優先される (同じなら新しい方を残す)
+    better: fn(&T, &T) -> bool,
+}
+
+impl<'a, T: 'a> Iterator for SlidingWindowExtremum<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.pos + self.width > self.a.len() {
+            return None;
+        }
+        let end = self.pos + self.width;
+        let start = if self.pos == 0 { 0 } else { end - 1 };
+        for i in start..end {
+            while let Some(&j) = self.deque.back() {
+                if (self.better)(&self.a[j], &self.a[i]) {
+                    break;
+                }
+                self.deque.pop_back();
+            }
+            self.deque.push_back(i);
+        }
+        while let Some(&j) = self.deque.front() {
+            if self.pos <= j {
                 break;
             }
+            self.deque.pop_front();
         }
-        set.push_back((e, i));
+        self.pos += 1;
+        self.deque.front().map(|&j| &self.a[j])
     }
-    for (s, e) in a.iter().skip(width).enumerate() {
-        res.push(set.front().expect("sliding_minimum error").0.clone());
-        while let Some((v, i)) = set.pop_back() {
-            if v < e {
-                set.push_back((v, i));
-                break;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = (self.a.len() + 1).saturating_sub(self.pos + self.width);
+        (rest, Some(rest))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for SlidingWindowExtremum<'a, T> {}
+
+fn sliding_min<'a, T: PartialOrd>(a: &'a [T], width: usize) -> SlidingWindowExtremum<'a, T> {
+    assert!(width > 0, "sliding window width must be positive");
+    fn less<T: PartialOrd>(x: &T, y: &T) -> bool {
+        x < y
+    }
+    SlidingWindowExtremum {
+        a: a,
+        width: width,
+        pos: 0,
+        deque: std::collections::VecDeque::new(),
+        better: less::<T>,
+    }
+}
+
+fn sliding_max<'a, T: PartialOrd>(a: &'a [T], width: usize) -> SlidingWindowExtremum<'a, T> {
+    assert!(width > 0, "sliding window width must be positive");
+    fn greater<T: PartialOrd>(x: &T, y: &T) -> bool {
+        x > y
+    }
+    SlidingWindowExtremum {
+        a: a,
+        width: width,
+        pos: 0,
+        deque: std::collections::VecDeque::new(),
+        better: greater::<T>,
+    }
+}
+
+trait Monoid {
+    fn unity() -> Self;
+    fn op(&self, other: &Self) -> Self;
+}
+
+// Sliding Window Aggregation
+// 2 本のスタックで queue を表し, 中身を古い順に op した値を償却 O(1) で返す
+// op は可換でなくてよい
+#[derive(Clone, Debug)]
+struct SlidingWindowAggregation<T> {
+    // (値, その値から front の末尾までの総積) を古いものが上に来るように積む
+    front: Vec<(T, T)>,
+    back: Vec<T>,
+    back_fold: T,
+}
+
+impl<T: Monoid + Clone> SlidingWindowAggregation<T> {
+    fn new() -> Self {
+        SlidingWindowAggregation {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_fold: T::unity(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, x: T) {
+        self.back_fold = self.back_fold.op(&x);
+        self.back.push(x);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            let mut fold = T::unity();
+            while let Some(x) = self.back.pop() {
+                fold = x.op(&fold);
+                self.front.push((x, fold.clone()));
             }
+            self.back_fold = T::unity();
         }
-        set.push_back((e, s+width));
-        while let Some((v, i)) = set.pop_front() {
-            if s+1 <= i {
-                set.push_front((v, i));
-                break;
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    fn fold(&self) -> T {
+        match self.front.last() {
+            Some(&(_, ref f)) => f.op(&self.back_fold),
+            None => self.back_fold.clone(),
+        }
+    }
+}
+
+// Mo's algorithm
+// 区間は半開区間 [l, r) で, 答えはクエリを与えた順に返す
+// add(s, i) / remove(s, i): 位置 i を区間に加える / 区間から除く
+fn hilbert_order(x: usize, y: usize, log: u32) -> u64 {
+    let (mut x, mut y) = (x as u64, y as u64);
+    let n = 1u64 << log;
+    let mut d = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = (x & s > 0) as u64;
+        let ry = (y & s > 0) as u64;
+        d += s * s * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+fn mo<S, R, Add, Remove, Answer>(
+    state: &mut S,
+    queries: &Vec<(usize, usize)>,
+    mut add: Add,
+    mut remove: Remove,
+    mut answer: Answer,
+) -> Vec<R>
+where
+    Add: FnMut(&mut S, usize),
+    Remove: FnMut(&mut S, usize),
+    Answer: FnMut(&S) -> R,
+{
+    let q = queries.len();
+    let n = queries.iter().map(|&(_, r)| r).max().unwrap_or(0);
+    let mut log = 0;
+    while (1usize << log) <= n {
+        log += 1;
+    }
+    let mut order: Vec<usize> = (0..q).collect();
+    let key: Vec<u64> = queries.iter().map(|&(l, r)| hilbert_order(l, r, log)).collect();
+    order.sort_by_key(|&i| key[i]);
+
+    let mut res: Vec<Option<R>> = (0..q).map(|_| None).collect();
+    let (mut l, mut r) = (0, 0);
+    for i in order {
+        let (ql, qr) = queries[i];
+        assert!(ql <= qr);
+        while r < qr {
+            add(state, r);
+            r += 1;
+        }
+        while l > ql {
+            l -= 1;
+            add(state, l);
+        }
+        while r > qr {
+            r -= 1;
+            remove(state, r);
+        }
+        while l < ql {
+            remove(state, l);
+            l += 1;
+        }
+        res[i] = Some(answer(state));
+    }
+    res.into_iter().map(|x| x.unwrap()).collect()
+}
+
+// 一点更新つき Mo
+// queries[i] = (l, r, t): 更新 0..t を適用した後の [l, r) に対するクエリ
+// toggle(s, k, l, r): 更新 k を適用する (もう一度呼ぶと元に戻る).
+//   現在の区間が [l, r) なので, 更新位置が区間内なら s も更新すること
+fn mo_with_updates<S, R, Add, Remove, Toggle, Answer>(
+    state: &mut S,
+    n: usize,
+    queries: &Vec<(usize, usize, usize)>,
+    mut add: Add,
+    mut remove: Remove,
+    mut toggle: Toggle,
+    mut answer: Answer,
+) -> Vec<R>
+where
+    Add: FnMut(&mut S, usize),
+    Remove: FnMut(&mut S, usize),
+    Toggle: FnMut(&mut S, usize, usize, usize),
+    Answer: FnMut(&S) -> R,
+{
+    let q = queries.len();
+    let mut block = 1;
+    while block * block * block < n * n {
+        block += 1;
+    }
+    let mut order: Vec<usize> = (0..q).collect();
+    order.sort_by_key(|&i| {
+        let (l, r, t) = queries[i];
+        let lb = l / block;
+        let rb = r / block;
+        // 偶奇で向きを変えて時間方向の往復を減らす
+        let rb = if lb % 2 == 0 { rb } else { n / block + 1 - rb };
+        let t = if rb % 2 == 0 { t } else { std::usize::MAX - t };
+        (lb, rb, t)
+    });
+
+    let mut res: Vec<Option<R>> = (0..q).map(|_| None).collect();
+    let (mut l, mut r, mut t) = (0, 0, 0);
+    for i in order {
+        let (ql, qr, qt) = queries[i];
+        assert!(ql <= qr);
+        while r < qr {
+            add(state, r);
+            r += 1;
+        }
+        while l > ql {
+            l -= 1;
+            add(state, l);
+        }
+        while r > qr {
+            r -= 1;
+            remove(state, r);
+        }
+        while l < ql {
+            remove(state, l);
+            l += 1;
+        }
+        while t < qt {
+            toggle(state, t, l, r);
+            t += 1;
+        }
+        while t > qt {
+            t -= 1;
+            toggle(state, t, l, r);
+        }
+        res[i] = Some(answer(state));
+    }
+    res.into_iter().map(|x| x.unwrap()).collect()
+}
+
+// 削除のできない Mo (rollback のみ)
+// snapshot(s): 現在の状態を覚える, rollback(s): 直前の snapshot の状態に戻す
+// reset(s): 区間が空の状態に戻す
+fn mo_rollback<S, R, Add, Snapshot, Rollback, Reset, Answer>(
+    state: &mut S,
+    queries: &Vec<(usize, usize)>,
+    mut add: Add,
+    mut snapshot: Snapshot,
+    mut rollback: Rollback,
+    mut reset: Reset,
+    mut answer: Answer,
+) -> Vec<R>
+where
+    Add: FnMut(&mut S, usize),
+    Snapshot: FnMut(&mut S),
+    Rollback: FnMut(&mut S),
+    Reset: FnMut(&mut S),
+    Answer: FnMut(&S) -> R,
+{
+    let q = queries.len();
+    let n = queries.iter().map(|&(_, r)| r).max().unwrap_or(0);
+    let mut block = 1;
+    while block * block < n {
+        block += 1;
+    }
+    let mut order: Vec<usize> = (0..q).collect();
+    order.sort_by_key(|&i| (queries[i].0 / block, queries[i].1));
+
+    let mut res: Vec<Option<R>> = (0..q).map(|_| None).collect();
+    let mut cur_block = std::usize::MAX;
+    let mut r = 0;
+    for i in order {
+        let (ql, qr) = queries[i];
+        assert!(ql <= qr);
+        let b = ql / block;
+        let block_end = (b + 1) * block;
+        if qr <= block_end {
+            // 同じブロック内に収まるので愚直に
+            reset(state);
+            snapshot(state);
+            for j in ql..qr {
+                add(state, j);
             }
+            res[i] = Some(answer(state));
+            rollback(state);
+            cur_block = std::usize::MAX;
+            continue;
+        }
+        if cur_block != b {
+            reset(state);
+            cur_block = b;
+            r = block_end;
+        }
+        while r < qr {
+            add(state, r);
+            r += 1;
+        }
+        snapshot(state);
+        for j in (ql..block_end).rev() {
+            add(state, j);
         }
+        res[i] = Some(answer(state));
+        rollback(state);
     }
-    res.push(set.front().expect("sliding_minimum error").0.clone());
-    
-    res
+    res.into_iter().map(|x| x.unwrap()).collect()
 }
@@ -40,7 +40,7 @@ impl<T: Monoid + std::clone::Clone> SegmentTree<T> {
     }
 
     fn get(&self, ind: usize) -> T {
-        self.dat[self.dat.len()/2+ind-1]
+        self.dat[self.dat.len()/2+ind-1].clone()
     }
 
     fn query_(&self, l: usize, r: usize, i: usize, a: usize, b: usize) -> T {
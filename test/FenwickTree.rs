@@ -0,0 +1,60 @@
+// rustc --test test/FenwickTree.rs で実行する
+include!("../algebra.rs");
+include!("../FenwickTree.rs");
+
+fn xorshift(x: &mut u64) -> u64 {
+    *x ^= *x << 13;
+    *x ^= *x >> 7;
+    *x ^= *x << 17;
+    *x
+}
+
+#[test]
+fn range_add_small() {
+    let mut bit = RangeAddFenwickTree::<ModInt>::new(5);
+    bit.add(0, 5, mint(3));
+    assert_eq!(bit.range_sum(0, 5), mint(15));
+    assert_eq!(bit.range_sum(2, 5), mint(9));
+}
+
+// 区間加算と区間和を素朴な配列と比べる
+#[test]
+fn range_add_matches_naive() {
+    let mut seed = 88172645463325252;
+    for _ in 0..100 {
+        let n = (xorshift(&mut seed) % 20 + 1) as usize;
+        let mut naive = vec![0i64; n];
+        let mut bit = RangeAddFenwickTree::<ModInt>::new(n);
+        for _ in 0..100 {
+            let l = (xorshift(&mut seed) % (n as u64 + 1)) as usize;
+            let r = l + (xorshift(&mut seed) % (n - l + 1) as u64) as usize;
+            if xorshift(&mut seed) % 2 == 0 {
+                let v = (xorshift(&mut seed) % 100) as i64;
+                bit.add(l, r, mint(v));
+                for a in &mut naive[l..r] {
+                    *a += v;
+                }
+            } else {
+                assert_eq!(bit.range_sum(l, r), mint(naive[l..r].iter().sum()));
+            }
+        }
+    }
+}
+
+#[test]
+fn inversion_number_matches_naive() {
+    let mut seed = 88172645463325252;
+    for _ in 0..100 {
+        let n = (xorshift(&mut seed) % 30) as usize;
+        let a: Vec<u64> = (0..n).map(|_| xorshift(&mut seed) % 10).collect();
+        let mut expected = 0;
+        for i in 0..n {
+            for j in i + 1..n {
+                if a[i] > a[j] {
+                    expected += 1;
+                }
+            }
+        }
+        assert_eq!(inversion_number(&a), expected);
+    }
+}
@@ -0,0 +1,39 @@
+// rustc --test test/MaxFlow.rs で実行する
+include!("../Graph_new.rs");
+include!("../MaxFlow.rs");
+
+fn xorshift(x: &mut u64) -> u64 {
+    *x ^= *x << 13;
+    *x ^= *x >> 7;
+    *x ^= *x << 17;
+    *x
+}
+
+#[test]
+fn push_relabel_with_self_loop() {
+    let mut g = FlowNetwork::<i64>::new(2);
+    g.add_edge(0, 0, 1);
+    g.add_edge(0, 1, 3);
+    assert_eq!(g.max_flow_push_relabel(0, 1), 3);
+}
+
+// 自己ループと多重辺を含むランダムなグラフで Dinic と比べる
+#[test]
+fn push_relabel_matches_dinic() {
+    let mut seed = 88172645463325252;
+    for _ in 0..1000 {
+        let n = (xorshift(&mut seed) % 6 + 2) as usize;
+        let m = xorshift(&mut seed) % 15;
+        let mut a = FlowNetwork::<i64>::new(n);
+        for _ in 0..m {
+            let from = (xorshift(&mut seed) % n as u64) as usize;
+            let to = (xorshift(&mut seed) % n as u64) as usize;
+            let cap = (xorshift(&mut seed) % 10) as i64;
+            a.add_edge(from, to, cap);
+        }
+        let mut b = a.clone();
+        let s = (xorshift(&mut seed) % n as u64) as usize;
+        let t = (s + 1 + (xorshift(&mut seed) % (n as u64 - 1)) as usize) % n;
+        assert_eq!(a.max_flow_push_relabel(s, t), b.max_flow(s, t));
+    }
+}